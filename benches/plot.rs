@@ -0,0 +1,67 @@
+extern crate ascii_art;
+
+use std::time::{Duration, Instant};
+
+use ascii_art::{plot, CombinedObject, Coords, Dimensions, Line, PlottedCoords, Plottable, Representation};
+
+// a size x size scene crossed by `count` lines
+fn scene(size: u32, count: u32) -> CombinedObject {
+    let (dimensions, size, count) = (Dimensions(size, size), size as i32, count as i32);
+
+    let lines = (0..count)
+        .map(|i| {
+            let offset = (i * 7919) % size;
+            let line: Box<dyn Plottable> = match i % 3 {
+                0 => Box::new(Line::new(Coords(0, offset), Coords(size - 1, size - 1 - offset)).unwrap()),
+                1 => Box::new(Line::new(Coords(offset, 0), Coords(offset, size - 1)).unwrap()),
+                _ => Box::new(Line::new(Coords(0, offset), Coords(size - 1, offset)).unwrap()),
+            };
+
+            line
+        })
+        .collect();
+
+    CombinedObject(dimensions, lines)
+}
+
+// how plot() used to work: look up the representation of every single cell
+fn plot_per_cell(a: Box<dyn Plottable>) -> Vec<PlottedCoords> {
+    let mut canvas_coords = Coords::from_dimensions(a.get_dimensions())
+        .iter()
+        .map(|fill_coords| {
+            match a.get_representation_at(*fill_coords) {
+                Some(representation) => PlottedCoords(fill_coords.0 as u32, fill_coords.1 as u32, representation, None),
+                None => PlottedCoords(fill_coords.0 as u32, fill_coords.1 as u32, Representation::Canvas, None),
+            }
+        }).collect::<Vec<_>>();
+
+    canvas_coords.sort_by_key(|&PlottedCoords(x, y, _, _)| (!y, x));
+    canvas_coords
+}
+
+// average time of `f`, the scene is built up front so only plotting is measured
+fn measure<F: FnMut(CombinedObject)>(iterations: u32, size: u32, count: u32, mut f: F) -> Duration {
+    let mut elapsed = Duration::new(0, 0);
+
+    for _ in 0..iterations {
+        let scene = scene(size, count);
+
+        let start = Instant::now();
+        f(scene);
+        elapsed += start.elapsed();
+    }
+
+    elapsed / iterations
+}
+
+fn main() {
+    let per_cell = measure(3, 100, 50, |scene| { plot_per_cell(Box::new(scene)); });
+    let rasterized = measure(3, 100, 50, |scene| { plot(Box::new(scene)).unwrap(); });
+
+    println!("100x100, 50 lines, per cell:       {:?}", per_cell);
+    println!("100x100, 50 lines, rasterized:     {:?}", rasterized);
+    println!("speedup: {:.0}x", per_cell.as_secs_f64() / rasterized.as_secs_f64());
+
+    let large = measure(3, 1000, 2000, |scene| { plot(Box::new(scene)).unwrap(); });
+    println!("1000x1000, 2000 lines, rasterized: {:?}", large);
+}
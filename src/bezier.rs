@@ -0,0 +1,324 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use line::Line;
+use plottable::Plottable;
+use raster::Raster;
+
+// a curve is subdivided until its control points are closer than this to the chord, in cells
+const FLATNESS: f64 = 0.25;
+const MAX_SUBDIVISIONS: u32 = 16;
+
+// -- structs --
+// the cells of a curve flattened into short lines, each cell is drawn with the glyph of the direction of the
+// curve at its center
+#[derive(Debug)]
+struct Curve {
+    dimensions: Dimensions,
+    coords: Vec<Coords>,
+    representations: HashMap<Coords, Representation>,
+    paths: Vec<Path>,
+}
+
+#[derive(Debug)]
+pub struct QuadraticBezier(Curve);
+
+#[derive(Debug)]
+pub struct CubicBezier(Curve);
+
+impl Curve {
+    fn new(control_points: &[Coords]) -> Result<Curve, Error> {
+        for control_point in control_points.iter() {
+            control_point.check_range()?;
+        }
+
+        let control_points = control_points.iter().map(|&Coords(x, y)| (f64::from(x), f64::from(y))).collect::<Vec<_>>();
+
+        let mut points = vec![(control_points[0], 0.0)];
+        flatten(&control_points, (0.0, 1.0), 0, &mut points);
+
+        let mut coords = vec![];
+        let mut representations = HashMap::new();
+        let mut paths = vec![];
+
+        let ((x, y), _) = points[0];
+        let mut start = Coords::from_point(x, y)?;
+        let mut start_point = points[0];
+
+        for &((x, y), t) in points.iter().skip(1) {
+            let end = Coords::from_point(x, y)?;
+
+            // points rounding to the same cell are merged, the direction spans all of them
+            if end == start {
+                continue;
+            }
+
+            let ((start_x, start_y), start_t) = start_point;
+            let (dx, dy) = (x - start_x, y - start_y);
+            let representation = Representation::from_direction(dx, dy);
+
+            for segment_coords in Line::new(start, end)?.get_coords() {
+                if let Entry::Vacant(entry) = representations.entry(segment_coords) {
+                    // the curve passes the cell where its center is projected onto the chord
+                    let (cx, cy) = (f64::from(segment_coords.0) - start_x, f64::from(segment_coords.1) - start_y);
+                    let along = ((cx * dx + cy * dy) / (dx * dx + dy * dy)).clamp(0.0, 1.0);
+
+                    let (tangent_x, tangent_y) = get_tangent(&control_points, start_t + along * (t - start_t));
+
+                    // the tangent vanishes where a control point is on an end point
+                    entry.insert(if tangent_x == 0.0 && tangent_y == 0.0 { representation.clone() } else { Representation::from_direction(tangent_x, tangent_y) });
+                    coords.push(segment_coords);
+                }
+            }
+
+            paths.push(Path(representation, vec![start, end]));
+
+            start = end;
+            start_point = ((x, y), t);
+        }
+
+        // a curve which doesn't leave its first cell is just that cell
+        if coords.is_empty() {
+            let (dx, dy) = (control_points[control_points.len() - 1].0 - control_points[0].0, control_points[control_points.len() - 1].1 - control_points[0].1);
+            let representation = Representation::from_direction(dx, dy);
+
+            representations.insert(start, representation.clone());
+            coords.push(start);
+            paths.push(Path(representation, vec![start]));
+        }
+
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Curve { dimensions, coords, representations, paths })
+    }
+}
+
+impl QuadraticBezier {
+    pub fn new(start: Coords, control: Coords, end: Coords) -> Result<QuadraticBezier, Error> {
+        Ok(QuadraticBezier(Curve::new(&[start, control, end])?))
+    }
+}
+
+impl CubicBezier {
+    pub fn new(start: Coords, first_control: Coords, second_control: Coords, end: Coords) -> Result<CubicBezier, Error> {
+        Ok(CubicBezier(Curve::new(&[start, first_control, second_control, end])?))
+    }
+}
+
+impl Plottable for Curve {
+    fn get_dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.coords)
+    }
+
+    // ordered from the start to the end of the curve
+    fn get_coords(&self) -> Vec<Coords> {
+        self.coords.clone()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.representations.get(&coords).cloned()
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.paths.clone()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.coords.iter() {
+            raster.set(*coords, self.representations[coords].clone());
+        }
+    }
+}
+
+impl Plottable for QuadraticBezier {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0.get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.0.get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.0.get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.0.get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.0.get_paths()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        self.0.rasterize(raster)
+    }
+}
+
+impl Plottable for CubicBezier {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0.get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.0.get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.0.get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.0.get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.0.get_paths()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        self.0.rasterize(raster)
+    }
+}
+
+// -- functions --
+// appends the end points of the chords approximating the curve with their parameter t of the whole curve, the
+// start point is expected to be appended already. the part of the curve spans `range` of t
+fn flatten(control_points: &[(f64, f64)], range: (f64, f64), depth: u32, points: &mut Vec<((f64, f64), f64)>) {
+    let start = control_points[0];
+    let end = control_points[control_points.len() - 1];
+
+    let is_flat = control_points[1..control_points.len() - 1].iter().all(|&point| distance_to_chord(point, start, end) <= FLATNESS);
+
+    if is_flat || depth >= MAX_SUBDIVISIONS {
+        points.push((end, range.1));
+        return;
+    }
+
+    // de Casteljau's algorithm splits the curve in half, the first points of every level form the
+    // first half and the last points the second half
+    let mut level = control_points.to_vec();
+    let mut first_half = vec![level[0]];
+    let mut second_half = vec![level[level.len() - 1]];
+
+    while level.len() > 1 {
+        level = level.windows(2).map(|pair| ((pair[0].0 + pair[1].0) / 2.0, (pair[0].1 + pair[1].1) / 2.0)).collect();
+
+        first_half.push(level[0]);
+        second_half.push(level[level.len() - 1]);
+    }
+
+    second_half.reverse();
+
+    let middle = (range.0 + range.1) / 2.0;
+    flatten(&first_half, (range.0, middle), depth + 1, points);
+    flatten(&second_half, (middle, range.1), depth + 1, points);
+}
+
+// the direction of the curve at t, the last two points of de Casteljau's algorithm lie on the tangent
+fn get_tangent(control_points: &[(f64, f64)], t: f64) -> (f64, f64) {
+    let mut level = control_points.to_vec();
+
+    while level.len() > 2 {
+        level = level.windows(2).map(|pair| (pair[0].0 + t * (pair[1].0 - pair[0].0), pair[0].1 + t * (pair[1].1 - pair[0].1))).collect();
+    }
+
+    (level[1].0 - level[0].0, level[1].1 - level[0].1)
+}
+
+fn distance_to_chord(point: (f64, f64), start: (f64, f64), end: (f64, f64)) -> f64 {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = dx.hypot(dy);
+
+    if length == 0.0 {
+        return (point.0 - start.0).hypot(point.1 - start.1);
+    }
+
+    ((point.0 - start.0) * dy - (point.1 - start.1) * dx).abs() / length
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+
+    fn assert_connected(coords: &[Coords]) {
+        for pair in coords.windows(2) {
+            assert!((pair[0].0 - pair[1].0).abs() <= 1 && (pair[0].1 - pair[1].1).abs() <= 1, "{:?} is not connected", pair);
+        }
+    }
+
+    #[test]
+    fn test_quadratic_bezier() {
+        let curve = QuadraticBezier::new(Coords(0, 0), Coords(6, 6), Coords(12, 0)).unwrap();
+        let coords = curve.get_coords();
+
+        assert_connected(&coords);
+        assert_eq!(Coords(0, 0), coords[0]);
+        assert_eq!(Coords(12, 0), coords[coords.len() - 1]);
+        assert_eq!(Dimensions(13, 4), curve.get_dimensions());
+
+        assert_eq!(Some(Representation::DiagonalLineLeftToRight), curve.get_representation_at(Coords(0, 0)));
+        assert_eq!(Some(Representation::HorizontalLine), curve.get_representation_at(Coords(6, 3)));
+        assert_eq!(Some(Representation::DiagonalLineRightToLeft), curve.get_representation_at(Coords(12, 0)));
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let curve = CubicBezier::new(Coords(0, 0), Coords(8, 0), Coords(0, 8), Coords(8, 8)).unwrap();
+        let coords = curve.get_coords();
+
+        assert_connected(&coords);
+        assert_eq!(Coords(0, 0), coords[0]);
+        assert_eq!(Coords(8, 8), coords[coords.len() - 1]);
+        assert_eq!(Some(Representation::HorizontalLine), curve.get_representation_at(Coords(0, 0)));
+        assert_eq!(Some(Representation::HorizontalLine), curve.get_representation_at(Coords(8, 8)));
+    }
+
+    #[test]
+    fn test_straight_bezier_is_a_line() {
+        let curve = CubicBezier::new(Coords(0, 0), Coords(1, 1), Coords(2, 2), Coords(3, 3)).unwrap();
+        let line = Line::new(Coords(0, 0), Coords(3, 3)).unwrap();
+
+        assert_eq!(line.get_coords(), curve.get_coords());
+        assert_eq!(1, curve.get_paths().len());
+    }
+
+    #[test]
+    fn test_render_quadratic_bezier() {
+        let curve = QuadraticBezier::new(Coords(0, 0), Coords(4, 4), Coords(8, 0)).unwrap();
+        let canvas = plot(Box::new(curve)).unwrap();
+
+        assert_eq!("    / - - - \\    \n  /           \\  \n/               \\\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_symmetric_bezier_has_mirrored_glyphs() {
+        let curve = QuadraticBezier::new(Coords(0, 0), Coords(10, 12), Coords(20, 0)).unwrap();
+
+        let mirrored = |representation| match representation {
+            Representation::DiagonalLineLeftToRight => Representation::DiagonalLineRightToLeft,
+            Representation::DiagonalLineRightToLeft => Representation::DiagonalLineLeftToRight,
+            other => other,
+        };
+
+        // the cells of both halves can round differently, their glyphs are compared where both have a cell
+        for Coords(x, y) in curve.get_coords() {
+            if let Some(representation) = curve.get_representation_at(Coords(20 - x, y)) {
+                assert_eq!(curve.get_representation_at(Coords(x, y)).map(mirrored), Some(representation), "at {:?}", Coords(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bezier_out_of_range() {
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(i32::MAX, 0))), QuadraticBezier::new(Coords(0, 0), Coords(i32::MAX, 0), Coords(1, 1)).map(|_| ()));
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+
+use canvas::Canvas;
+use coords::{Dimensions, PlottedCoords, Representation};
+
+// the bits of the cells of a 1 by 2 block, top to bottom, and the half block for every combination
+const HALF_BLOCK_BITS: [[u32; 1]; 2] = [[0x1], [0x2]];
+const HALF_BLOCKS: [char; 4] = [' ', '▀', '▄', '█'];
+
+// the bits of the cells of a 2 by 2 block, top left, top right, bottom left and bottom right, and the
+// quadrant block for every combination
+const QUADRANT_BITS: [[u32; 2]; 2] = [[0x1, 0x2], [0x4, 0x8]];
+const QUADRANTS: [char; 16] = [' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█'];
+
+// -- functions --
+pub fn render_half_blocks(canvas: &Canvas) -> String {
+    let mut output = vec![];
+    render_half_blocks_to(canvas, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("block elements are valid utf-8")
+}
+
+// every character covers 1 by 2 cells of the canvas, which makes the cells roughly square in most terminals
+pub fn render_half_blocks_to<W: Write>(canvas: &Canvas, writer: &mut W) -> io::Result<()> {
+    render_blocks_to(canvas, &HALF_BLOCK_BITS, |pattern| HALF_BLOCKS[pattern as usize], writer)
+}
+
+pub fn render_quadrants(canvas: &Canvas) -> String {
+    let mut output = vec![];
+    render_quadrants_to(canvas, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("block elements are valid utf-8")
+}
+
+// every character covers 2 by 2 cells of the canvas
+pub fn render_quadrants_to<W: Write>(canvas: &Canvas, writer: &mut W) -> io::Result<()> {
+    render_blocks_to(canvas, &QUADRANT_BITS, |pattern| QUADRANTS[pattern as usize], writer)
+}
+
+// draws blocks of cells as one character each. the bits of a block are given top to bottom, left to right
+// and the block is drawn with the glyph of the bits of its cells which are not Representation::Canvas.
+// the blocks are aligned to the bottom left of the canvas
+pub(crate) fn render_blocks_to<W, F, B>(canvas: &Canvas, bits: &[B], glyph: F, writer: &mut W) -> io::Result<()>
+    where W: Write, F: Fn(u32) -> char, B: AsRef<[u32]>
+{
+    let Dimensions(width, height) = canvas.0;
+    let (block_width, block_height) = (bits[0].as_ref().len() as u32, bits.len() as u32);
+
+    let columns = width.div_ceil(block_width);
+    let rows = height.div_ceil(block_height);
+
+    let mut patterns = vec![0; columns as usize * rows as usize];
+
+    for &PlottedCoords(x, y, ref representation, _) in canvas.1.iter() {
+        if *representation == Representation::Canvas {
+            continue;
+        }
+
+        let y = canvas.get_row_from_bottom(y as i32) as u32;
+        let (column, row) = (x / block_width, y / block_height);
+        let bit = bits[(block_height - 1 - y % block_height) as usize].as_ref()[(x % block_width) as usize];
+
+        patterns[row as usize * columns as usize + column as usize] |= bit;
+    }
+
+    for row in (0..rows).rev() {
+        let start = row as usize * columns as usize;
+
+        for pattern in patterns[start..start + columns as usize].iter() {
+            write!(writer, "{}", glyph(*pattern))?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, plot_in, plot_sparse, CoordinateSystem};
+    use coords::Coords;
+    use line::Line;
+    use rectangle::Rectangle;
+
+    #[test]
+    fn test_render_half_blocks() {
+        let canvas = plot(Box::new(Line::new(Coords(0, 0), Coords(2, 2)).unwrap())).unwrap();
+
+        // the top row of cells is the lower half of a block reaching above the canvas
+        assert_eq!("  ▄\n▄▀ \n", render_half_blocks(&canvas));
+    }
+
+    #[test]
+    fn test_render_filled_rectangle_solid() {
+        let rectangle = Rectangle::new(Coords(0, 0), Coords(3, 3)).unwrap().filled();
+        let canvas = plot(Box::new(rectangle)).unwrap();
+
+        assert_eq!("████\n████\n", render_half_blocks(&canvas));
+        assert_eq!("██\n██\n", render_quadrants(&canvas));
+    }
+
+    #[test]
+    fn test_render_quadrants() {
+        let canvas = plot_sparse(Box::new(Line::new(Coords(0, 0), Coords(3, 3)).unwrap())).unwrap();
+
+        assert_eq!(" ▞\n▞ \n", render_quadrants(&canvas));
+    }
+
+    #[test]
+    fn test_render_blocks_in_screen_coordinates() {
+        let line = Line::new(Coords(0, 0), Coords(3, 3)).unwrap();
+        let canvas = plot_in(Box::new(line), CoordinateSystem::Screen).unwrap();
+
+        assert_eq!("▚ \n ▚\n", render_quadrants(&canvas));
+    }
+}
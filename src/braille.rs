@@ -0,0 +1,57 @@
+use std::io::{self, Write};
+
+use blocks::render_blocks_to;
+use canvas::Canvas;
+
+// the braille patterns start at U+2800, each of the 8 dots is a bit. the dots are numbered top to bottom
+// in the left column, then the right column, with the bottom row added last
+const BRAILLE_BLANK: u32 = 0x2800;
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// -- functions --
+pub fn render_braille(canvas: &Canvas) -> String {
+    let mut output = vec![];
+    render_braille_to(canvas, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("braille patterns are valid utf-8")
+}
+
+// every character covers 2 by 4 cells of the canvas, every cell which is not Representation::Canvas is a dot
+pub fn render_braille_to<W: Write>(canvas: &Canvas, writer: &mut W) -> io::Result<()> {
+    render_blocks_to(canvas, &DOTS, |pattern| char::from_u32(BRAILLE_BLANK + pattern).expect("braille patterns are valid chars"), writer)
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, plot_sparse};
+    use circle::Circle;
+    use coords::Coords;
+    use line::Line;
+
+    #[test]
+    fn test_render_braille_line() {
+        let canvas = plot(Box::new(Line::new(Coords(0, 0), Coords(3, 0)).unwrap())).unwrap();
+
+        assert_eq!("⣀⣀\n", render_braille(&canvas));
+    }
+
+    #[test]
+    fn test_render_braille_dot_positions() {
+        let canvas = plot(Box::new(Line::new(Coords(0, 0), Coords(3, 3)).unwrap())).unwrap();
+
+        assert_eq!("⡠⠊\n", render_braille(&canvas));
+    }
+
+    #[test]
+    fn test_render_braille_partial_blocks() {
+        // 5 by 5 cells need 3 by 2 characters, the blocks start at the bottom left
+        let canvas = plot(Box::new(Circle::new(Coords(2, 2), 2).unwrap())).unwrap();
+        let rendered = render_braille(&canvas);
+
+        assert_eq!(2, rendered.lines().count());
+        assert!(rendered.lines().all(|line| line.chars().count() == 3));
+        assert_eq!(render_braille(&plot_sparse(Box::new(Circle::new(Coords(2, 2), 2).unwrap())).unwrap()), rendered);
+    }
+}
@@ -0,0 +1,996 @@
+use std::collections::{HashSet, VecDeque};
+use std::io::{self, Write};
+
+use circle::TERMINAL_CELL_ASPECT_RATIO;
+use coords::{Coords, Dimensions, PlottedCoords, Representation};
+use error::Error;
+use junction::{connect, Arms};
+use plottable::Plottable;
+use raster::Raster;
+use style::{supports_color, Style, RESET};
+
+// the plotted coords are ordered top to bottom, left to right and translated so the canvas starts at
+// (0, 0) even if the plotted object extends into negative space. a sparse canvas only lists the coords
+// which are not Representation::Canvas
+#[derive(Debug)]
+pub struct Canvas(pub Dimensions, pub Vec<PlottedCoords>, pub CoordinateSystem);
+
+// which way the y axis points. y = 0 is the bottom row of a cartesian canvas and the top row of a screen
+// canvas, which draws every object upside down compared to a cartesian one
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum CoordinateSystem {
+    #[default]
+    Cartesian,
+    Screen,
+}
+
+impl CoordinateSystem {
+    // sorts plotted coords top to bottom, left to right
+    pub(crate) fn get_sort_key(self, x: u32, y: u32) -> (u32, u32) {
+        match self {
+            CoordinateSystem::Cartesian => (!y, x),
+            CoordinateSystem::Screen => (y, x),
+        }
+    }
+}
+
+// which neighbours of a cell flood_fill() spreads to, eight also spreads diagonally
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+// how the cells of a canvas are laid out as text. by default every glyph is followed by a space, which
+// makes the cells roughly square in most terminals
+#[derive(Debug, PartialEq, Clone)]
+pub struct RenderOptions {
+    separator: String,
+    stretch: u32,
+    trim: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> RenderOptions {
+        RenderOptions { separator: " ".to_string(), stretch: 1, trim: false }
+    }
+}
+
+impl RenderOptions {
+    pub fn new() -> RenderOptions {
+        RenderOptions::default()
+    }
+
+    // written between the cells of a row, may be empty
+    pub fn separator(mut self, separator: &str) -> RenderOptions {
+        self.separator = separator.to_string();
+        self
+    }
+
+    // every cell is this many characters wide, lines and fills are continued to the right and anything
+    // else is padded with spaces. a factor of 0 is treated as 1
+    pub fn stretch(mut self, stretch: u32) -> RenderOptions {
+        self.stretch = stretch.max(1);
+        self
+    }
+
+    // removes the whitespace at the end of every row
+    pub fn trim(mut self) -> RenderOptions {
+        self.trim = true;
+        self
+    }
+
+    // the aspect ratio (height / width) of a rendered cell, which is a terminal character tall and as wide as
+    // the stretched glyph and the separator. see Circle::with_aspect_ratio()
+    pub fn get_aspect_ratio(&self) -> f64 {
+        TERMINAL_CELL_ASPECT_RATIO / f64::from(self.stretch + self.separator.chars().count() as u32)
+    }
+}
+
+impl Canvas {
+    // the coords are in the coordinate system of the canvas
+    pub fn get_representation_at(&self, coords: Coords) -> Representation {
+        if coords.0 < 0 || coords.1 < 0 {
+            return Representation::Canvas;
+        }
+
+        let (x, y) = (coords.0 as u32, coords.1 as u32);
+        let system = self.2;
+
+        match self.1.binary_search_by_key(&system.get_sort_key(x, y), |&PlottedCoords(x, y, _, _)| system.get_sort_key(x, y)) {
+            Ok(i) => self.1[i].2.clone(),
+            Err(_) => Representation::Canvas,
+        }
+    }
+
+    // the row counted from the bottom of the canvas, and the other way round
+    pub(crate) fn get_row_from_bottom(&self, y: i32) -> i32 {
+        match self.2 {
+            CoordinateSystem::Cartesian => y,
+            CoordinateSystem::Screen => (self.0).1 as i32 - 1 - y,
+        }
+    }
+
+    // the same cells in the other coordinate system. every object keeps its coords, so it ends up upside
+    // down and its glyphs are flipped with it
+    pub fn into_coordinate_system(self, system: CoordinateSystem) -> Canvas {
+        if self.2 == system {
+            return self;
+        }
+
+        let Canvas(dimensions, plotted_coords, _) = self;
+
+        let mut plotted_coords = plotted_coords.into_iter()
+            .map(|PlottedCoords(x, y, representation, style)| PlottedCoords(x, y, representation.flip_vertically(), style))
+            .collect::<Vec<_>>();
+
+        plotted_coords.sort_by_key(|&PlottedCoords(x, y, _, _)| system.get_sort_key(x, y));
+        Canvas(dimensions, plotted_coords, system)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Charset {
+    canvas: char,
+    vertical_line: char,
+    horizontal_line: char,
+    diagonal_line_left_to_right: char,
+    diagonal_line_right_to_left: char,
+    circle: char,
+    top_left_corner: char,
+    top_right_corner: char,
+    bottom_left_corner: char,
+    bottom_right_corner: char,
+    rounded_top_left_corner: char,
+    rounded_top_right_corner: char,
+    rounded_bottom_left_corner: char,
+    rounded_bottom_right_corner: char,
+    vertex: char,
+    fill: char,
+    cross: char,
+    tee_up: char,
+    tee_down: char,
+    tee_left: char,
+    tee_right: char,
+    // line cells are connected to the line cells next to them, see junction::connect()
+    connected: bool,
+}
+
+impl Charset {
+    pub fn ascii() -> Charset {
+        Charset {
+            canvas: ' ',
+            vertical_line: '|',
+            horizontal_line: '-',
+            diagonal_line_left_to_right: '/',
+            diagonal_line_right_to_left: '\\',
+            circle: 'o',
+            top_left_corner: '+',
+            top_right_corner: '+',
+            bottom_left_corner: '+',
+            bottom_right_corner: '+',
+            rounded_top_left_corner: '.',
+            rounded_top_right_corner: '.',
+            rounded_bottom_left_corner: '\'',
+            rounded_bottom_right_corner: '\'',
+            vertex: '+',
+            fill: '#',
+            cross: '+',
+            tee_up: '+',
+            tee_down: '+',
+            tee_left: '+',
+            tee_right: '+',
+            connected: false,
+        }
+    }
+
+    // light box-drawing characters
+    pub fn unicode() -> Charset {
+        Charset::light()
+    }
+
+    // box-drawing characters, the terminal has to support them
+    pub fn light() -> Charset {
+        Charset {
+            canvas: ' ',
+            vertical_line: '│',
+            horizontal_line: '─',
+            diagonal_line_left_to_right: '╱',
+            diagonal_line_right_to_left: '╲',
+            circle: 'o',
+            top_left_corner: '┌',
+            top_right_corner: '┐',
+            bottom_left_corner: '└',
+            bottom_right_corner: '┘',
+            rounded_top_left_corner: '╭',
+            rounded_top_right_corner: '╮',
+            rounded_bottom_left_corner: '╰',
+            rounded_bottom_right_corner: '╯',
+            vertex: '•',
+            fill: '█',
+            cross: '┼',
+            tee_up: '┴',
+            tee_down: '┬',
+            tee_left: '┤',
+            tee_right: '├',
+            connected: true,
+        }
+    }
+
+    // there are no heavy rounded corners, rounded corners are square
+    pub fn heavy() -> Charset {
+        Charset::light().with_glyphs(&[
+            (Representation::VerticalLine, '┃'),
+            (Representation::HorizontalLine, '━'),
+            (Representation::TopLeftCorner, '┏'),
+            (Representation::TopRightCorner, '┓'),
+            (Representation::BottomLeftCorner, '┗'),
+            (Representation::BottomRightCorner, '┛'),
+            (Representation::RoundedTopLeftCorner, '┏'),
+            (Representation::RoundedTopRightCorner, '┓'),
+            (Representation::RoundedBottomLeftCorner, '┗'),
+            (Representation::RoundedBottomRightCorner, '┛'),
+            (Representation::Cross, '╋'),
+            (Representation::TeeUp, '┻'),
+            (Representation::TeeDown, '┳'),
+            (Representation::TeeLeft, '┫'),
+            (Representation::TeeRight, '┣'),
+        ])
+    }
+
+    // there are no double rounded corners, rounded corners are square
+    pub fn double() -> Charset {
+        Charset::light().with_glyphs(&[
+            (Representation::VerticalLine, '║'),
+            (Representation::HorizontalLine, '═'),
+            (Representation::TopLeftCorner, '╔'),
+            (Representation::TopRightCorner, '╗'),
+            (Representation::BottomLeftCorner, '╚'),
+            (Representation::BottomRightCorner, '╝'),
+            (Representation::RoundedTopLeftCorner, '╔'),
+            (Representation::RoundedTopRightCorner, '╗'),
+            (Representation::RoundedBottomLeftCorner, '╚'),
+            (Representation::RoundedBottomRightCorner, '╝'),
+            (Representation::Cross, '╬'),
+            (Representation::TeeUp, '╩'),
+            (Representation::TeeDown, '╦'),
+            (Representation::TeeLeft, '╣'),
+            (Representation::TeeRight, '╠'),
+        ])
+    }
+
+    // light box-drawing characters with every corner rounded
+    pub fn rounded() -> Charset {
+        Charset::light().with_glyphs(&[
+            (Representation::TopLeftCorner, '╭'),
+            (Representation::TopRightCorner, '╮'),
+            (Representation::BottomLeftCorner, '╰'),
+            (Representation::BottomRightCorner, '╯'),
+        ])
+    }
+
+    // 'ascii', 'unicode', 'light', 'heavy', 'double' or 'rounded'
+    pub fn from_name(name: &str) -> Option<Charset> {
+        match name {
+            "ascii" => Some(Charset::ascii()),
+            "unicode" | "light" => Some(Charset::light()),
+            "heavy" => Some(Charset::heavy()),
+            "double" => Some(Charset::double()),
+            "rounded" => Some(Charset::rounded()),
+            _ => None,
+        }
+    }
+
+    fn with_glyphs(self, glyphs: &[(Representation, char)]) -> Charset {
+        glyphs.iter().fold(self, |charset, (representation, glyph)| charset.with_glyph(representation.clone(), *glyph))
+    }
+
+    pub fn with_glyph(mut self, representation: Representation, glyph: char) -> Charset {
+        match representation {
+            Representation::Canvas => self.canvas = glyph,
+            Representation::VerticalLine => self.vertical_line = glyph,
+            Representation::HorizontalLine => self.horizontal_line = glyph,
+            Representation::DiagonalLineLeftToRight => self.diagonal_line_left_to_right = glyph,
+            Representation::DiagonalLineRightToLeft => self.diagonal_line_right_to_left = glyph,
+            Representation::Circle => self.circle = glyph,
+            Representation::TopLeftCorner => self.top_left_corner = glyph,
+            Representation::TopRightCorner => self.top_right_corner = glyph,
+            Representation::BottomLeftCorner => self.bottom_left_corner = glyph,
+            Representation::BottomRightCorner => self.bottom_right_corner = glyph,
+            Representation::RoundedTopLeftCorner => self.rounded_top_left_corner = glyph,
+            Representation::RoundedTopRightCorner => self.rounded_top_right_corner = glyph,
+            Representation::RoundedBottomLeftCorner => self.rounded_bottom_left_corner = glyph,
+            Representation::RoundedBottomRightCorner => self.rounded_bottom_right_corner = glyph,
+            Representation::Vertex => self.vertex = glyph,
+            Representation::Fill => self.fill = glyph,
+            Representation::Cross => self.cross = glyph,
+            Representation::TeeUp => self.tee_up = glyph,
+            Representation::TeeDown => self.tee_down = glyph,
+            Representation::TeeLeft => self.tee_left = glyph,
+            Representation::TeeRight => self.tee_right = glyph,
+        }
+
+        self
+    }
+
+    pub fn get_glyph(&self, representation: &Representation) -> char {
+        match *representation {
+            Representation::Canvas => self.canvas,
+            Representation::VerticalLine => self.vertical_line,
+            Representation::HorizontalLine => self.horizontal_line,
+            Representation::DiagonalLineLeftToRight => self.diagonal_line_left_to_right,
+            Representation::DiagonalLineRightToLeft => self.diagonal_line_right_to_left,
+            Representation::Circle => self.circle,
+            Representation::TopLeftCorner => self.top_left_corner,
+            Representation::TopRightCorner => self.top_right_corner,
+            Representation::BottomLeftCorner => self.bottom_left_corner,
+            Representation::BottomRightCorner => self.bottom_right_corner,
+            Representation::RoundedTopLeftCorner => self.rounded_top_left_corner,
+            Representation::RoundedTopRightCorner => self.rounded_top_right_corner,
+            Representation::RoundedBottomLeftCorner => self.rounded_bottom_left_corner,
+            Representation::RoundedBottomRightCorner => self.rounded_bottom_right_corner,
+            Representation::Vertex => self.vertex,
+            Representation::Fill => self.fill,
+            Representation::Cross => self.cross,
+            Representation::TeeUp => self.tee_up,
+            Representation::TeeDown => self.tee_down,
+            Representation::TeeLeft => self.tee_left,
+            Representation::TeeRight => self.tee_right,
+        }
+    }
+}
+
+impl Default for Charset {
+    fn default() -> Charset {
+        Charset::ascii()
+    }
+}
+
+// -- functions --
+// plot() allocates every cell of the canvas, larger objects have to use plot_sparse()
+const MAX_CANVAS_CELLS: u64 = 1 << 28;
+
+pub fn plot(a: Box<dyn Plottable>) -> Result<Canvas, Error> {
+    let dimensions = a.get_dimensions();
+    let Dimensions(width, height) = dimensions;
+
+    if width == 0 || height == 0 {
+        return Err(Error::EmptyShape);
+    }
+
+    if u64::from(width) * u64::from(height) > MAX_CANVAS_CELLS {
+        return Err(Error::CanvasTooLarge(dimensions));
+    }
+
+    let mut raster = Raster::new(a.get_origin(), dimensions);
+    a.rasterize(&mut raster);
+
+    Ok(raster.into_canvas())
+}
+
+// plot() with y = 0 at the top of the canvas for a screen coordinate system, see CoordinateSystem
+pub fn plot_in(a: Box<dyn Plottable>, system: CoordinateSystem) -> Result<Canvas, Error> {
+    plot(a).map(|canvas| canvas.into_coordinate_system(system))
+}
+
+pub fn plot_sparse(a: Box<dyn Plottable>) -> Result<Canvas, Error> {
+    let dimensions = a.get_dimensions();
+
+    if dimensions.0 == 0 || dimensions.1 == 0 {
+        return Err(Error::EmptyShape);
+    }
+
+    let mut raster = Raster::sparse(a.get_origin(), dimensions);
+    a.rasterize(&mut raster);
+
+    Ok(raster.into_canvas())
+}
+
+pub fn plot_sparse_in(a: Box<dyn Plottable>, system: CoordinateSystem) -> Result<Canvas, Error> {
+    plot_sparse(a).map(|canvas| canvas.into_coordinate_system(system))
+}
+
+// fills the empty cells connected to the seed with the glyph and returns how many cells were filled. the filled
+// cells are Representation::Fill in a style with the glyph, so every region can have a glyph of its own. the seed
+// is in canvas coordinates like Canvas::get_representation_at(), nothing is filled if the seed is not an empty
+// cell. a fill spreading to more than max_cells cells is an error and leaves the canvas as it is, a large sparse
+// canvas would otherwise get every one of its cells listed
+pub fn flood_fill(canvas: &mut Canvas, seed: Coords, glyph: char, connectivity: Connectivity, max_cells: usize) -> Result<usize, Error> {
+    if max_cells == 0 {
+        return Err(Error::FillTooLarge(max_cells));
+    }
+
+    let Dimensions(width, height) = canvas.0;
+    let inside = |Coords(x, y): Coords| x >= 0 && y >= 0 && (x as u32) < width && (y as u32) < height;
+
+    if !inside(seed) {
+        return Err(Error::CoordsOutOfRange(seed));
+    }
+
+    if canvas.get_representation_at(seed) != Representation::Canvas {
+        return Ok(0);
+    }
+
+    let neighbours: &[(i32, i32)] = match connectivity {
+        Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+        Connectivity::Eight => &[(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)],
+    };
+
+    let mut filled = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    filled.insert(seed);
+    queue.push_back(seed);
+
+    while let Some(Coords(x, y)) = queue.pop_front() {
+        for &(dx, dy) in neighbours {
+            let neighbour = Coords(x + dx, y + dy);
+
+            if inside(neighbour) && !filled.contains(&neighbour) && canvas.get_representation_at(neighbour) == Representation::Canvas {
+                if filled.len() >= max_cells {
+                    return Err(Error::FillTooLarge(max_cells));
+                }
+
+                filled.insert(neighbour);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+
+    // a dense canvas already lists every cell, a sparse canvas gets the filled cells added
+    let mut added = vec![];
+    let system = canvas.2;
+    let style = Some(Style::new().glyph(glyph));
+
+    for &Coords(x, y) in filled.iter() {
+        let (x, y) = (x as u32, y as u32);
+
+        match canvas.1.binary_search_by_key(&system.get_sort_key(x, y), |&PlottedCoords(x, y, _, _)| system.get_sort_key(x, y)) {
+            Ok(i) => canvas.1[i] = PlottedCoords(x, y, Representation::Fill, style.clone()),
+            Err(_) => added.push(PlottedCoords(x, y, Representation::Fill, style.clone())),
+        }
+    }
+
+    if !added.is_empty() {
+        canvas.1.extend(added);
+        canvas.1.sort_by_key(|&PlottedCoords(x, y, _, _)| system.get_sort_key(x, y));
+    }
+
+    Ok(filled.len())
+}
+
+pub fn render(canvas: &Canvas, charset: &Charset) -> String {
+    let mut output = vec![];
+    render_to(canvas, charset, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("rendered glyphs are valid utf-8")
+}
+
+pub fn render_to<W: Write>(canvas: &Canvas, charset: &Charset, writer: &mut W) -> io::Result<()> {
+    render_cells_to(canvas, charset, &RenderOptions::new(), writer, false)
+}
+
+pub fn render_with(canvas: &Canvas, charset: &Charset, options: &RenderOptions) -> String {
+    let mut output = vec![];
+    render_with_to(canvas, charset, options, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("rendered glyphs are valid utf-8")
+}
+
+pub fn render_with_to<W: Write>(canvas: &Canvas, charset: &Charset, options: &RenderOptions, writer: &mut W) -> io::Result<()> {
+    render_cells_to(canvas, charset, options, writer, false)
+}
+
+pub fn render_ansi(canvas: &Canvas, charset: &Charset) -> String {
+    let mut output = vec![];
+    render_ansi_to(canvas, charset, &mut output).expect("writing to a Vec never fails");
+
+    String::from_utf8(output).expect("rendered glyphs are valid utf-8")
+}
+
+// like render_to() with every glyph drawn in the style of its cell by ANSI escape sequences. the spaces
+// between the glyphs are never styled
+pub fn render_ansi_to<W: Write>(canvas: &Canvas, charset: &Charset, writer: &mut W) -> io::Result<()> {
+    render_cells_to(canvas, charset, &RenderOptions::new(), writer, true)
+}
+
+pub fn render_ansi_with_to<W: Write>(canvas: &Canvas, charset: &Charset, options: &RenderOptions, writer: &mut W) -> io::Result<()> {
+    render_cells_to(canvas, charset, options, writer, true)
+}
+
+fn render_cells_to<W: Write>(canvas: &Canvas, charset: &Charset, options: &RenderOptions, writer: &mut W, styled: bool) -> io::Result<()> {
+    let Dimensions(width, height) = canvas.0;
+    let mut plotted_coords = canvas.1.iter().peekable();
+
+    for row_from_bottom in (0..height as i32).rev() {
+        let y = canvas.get_row_from_bottom(row_from_bottom) as u32;
+
+        // the whole row is looked up first, a line is only continued into the next cell if that connects back
+        let cells = (0..width)
+            .map(|x| {
+                // cells missing from a sparse canvas are empty
+                let (representation, style) = match plotted_coords.peek() {
+                    Some(&&PlottedCoords(plotted_x, plotted_y, ref representation, ref style)) if plotted_x == x && plotted_y == y => {
+                        plotted_coords.next();
+                        (representation, style.as_ref())
+                    },
+                    _ => (&Representation::Canvas, None),
+                };
+
+                // junction::connect() expects the rows to count from the bottom
+                let representation = if charset.connected {
+                    let representation_at = |Coords(x, row_from_bottom)| canvas.get_representation_at(Coords(x, canvas.get_row_from_bottom(row_from_bottom)));
+                    connect(Coords(x as i32, row_from_bottom), representation, representation_at)
+                } else {
+                    representation.clone()
+                };
+
+                (representation, style)
+            })
+            .collect::<Vec<_>>();
+
+        // the text of the row in the style it is written in, the separators have none
+        let mut pieces = vec![];
+
+        for (x, (representation, style)) in cells.iter().enumerate() {
+            // the glyphs of the object win over the charset, even when drawn without colors
+            let glyph_of = |representation: &Representation| style.and_then(|style| style.get_glyph(representation)).unwrap_or_else(|| charset.get_glyph(representation));
+            let chr = glyph_of(representation);
+
+            let continued = Arms::of(representation).reaches_right() && cells.get(x + 1).is_some_and(|(next, _)| Arms::of(next).reaches_left());
+
+            let padding = if continued {
+                glyph_of(&Representation::HorizontalLine)
+            } else if *representation == Representation::Fill {
+                chr
+            } else {
+                ' '
+            };
+
+            pieces.push(((1..options.stretch).fold(chr.to_string(), |mut cell, _| { cell.push(padding); cell }), *style));
+
+            if x + 1 < cells.len() { pieces.push((options.separator.clone(), None)); }
+        }
+
+        // the whitespace is trimmed before the escape sequences are added, which would end the row otherwise
+        if options.trim {
+            trim_end(&mut pieces);
+        }
+
+        let mut row = String::new();
+
+        for (text, style) in pieces {
+            match style {
+                Some(style) if styled && !style.get_escape_sequence().is_empty() => {
+                    row.push_str(&format!("{}{}{}", style.get_escape_sequence(), text, RESET));
+                },
+                _ => row.push_str(&text),
+            }
+        }
+
+        writeln!(writer, "{}", row)?;
+    }
+
+    Ok(())
+}
+
+// removes the whitespace at the end of the text of a row, pieces left empty are dropped
+fn trim_end(pieces: &mut Vec<(String, Option<&Style>)>) {
+    while let Some((text, _)) = pieces.last_mut() {
+        let length = text.trim_end().len();
+
+        if length > 0 {
+            text.truncate(length);
+            return;
+        }
+
+        pieces.pop();
+    }
+}
+
+pub fn draw(canvas: Canvas) {
+    draw_with(canvas, &Charset::ascii())
+}
+
+pub fn draw_with(canvas: Canvas, charset: &Charset) {
+    draw_with_options(canvas, charset, &RenderOptions::new())
+}
+
+// styled cells are drawn in color unless stdout is not a terminal or NO_COLOR is set, see supports_color()
+pub fn draw_with_options(canvas: Canvas, charset: &Charset, options: &RenderOptions) {
+    let stdout = io::stdout();
+
+    if supports_color(&stdout) {
+        render_ansi_with_to(&canvas, charset, options, &mut stdout.lock()).expect("could not write to stdout");
+    } else {
+        render_with_to(&canvas, charset, options, &mut stdout.lock()).expect("could not write to stdout");
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use circle::Circle;
+    use fixtures::*;
+    use line::Line;
+    use plottable::{combine, CombinedObject};
+    use rectangle::Rectangle;
+    use style::{Color, Style, Styled};
+
+    #[test]
+    fn test_plot_merged_object() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let line_2 = fixtures_horizontal_line();
+        let line_3 = fixtures_vertical_line();
+
+        // combined object is supposed to be large enough to contain 2 lines
+        let lines_combined_1 = combine(Box::new(line_1), Box::new(line_2)).unwrap();
+        let lines_combined_2 = combine(Box::new(lines_combined_1), Box::new(line_3)).unwrap();
+
+        let canvas = plot(Box::new(lines_combined_2)).unwrap();
+
+        assert_eq!((canvas.0).0, 3);
+        assert_eq!((canvas.0).1, 4);
+
+        assert_eq!((canvas.1).len(), 12);
+
+        assert_eq!(canvas.1[0], PlottedCoords(0, 3, Representation::VerticalLine, None));
+        assert_eq!(canvas.1[1], PlottedCoords(1, 3, Representation::Canvas, None));
+        assert_eq!(canvas.1[2], PlottedCoords(2, 3, Representation::Canvas, None));
+
+        assert_eq!(canvas.1[3], PlottedCoords(0, 2, Representation::VerticalLine, None));
+        assert_eq!(canvas.1[4], PlottedCoords(1, 2, Representation::Canvas, None));
+        assert_eq!(canvas.1[5], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, None));
+
+        assert_eq!(canvas.1[6], PlottedCoords(0, 1, Representation::VerticalLine, None));
+        assert_eq!(canvas.1[7], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[8], PlottedCoords(2, 1, Representation::Canvas, None));
+
+        assert_eq!(canvas.1[9], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[10], PlottedCoords(1, 0, Representation::HorizontalLine, None));
+        assert_eq!(canvas.1[11], PlottedCoords(2, 0, Representation::HorizontalLine, None));
+    }
+
+    #[test]
+    fn test_plot_line() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let line_2 = fixtures_horizontal_line();
+
+        // combined object is supposed to be large enough to contain 2 lines
+        let lines_combined = combine(Box::new(line_1), Box::new(line_2)).unwrap();
+        let canvas = plot(Box::new(lines_combined)).unwrap();
+
+        assert_eq!((canvas.0).0, 3);
+        assert_eq!((canvas.0).1, 3);
+
+        assert_eq!((canvas.1).len(), 9);
+
+        assert_eq!(canvas.1[0], PlottedCoords(0, 2, Representation::Canvas, None));
+        assert_eq!(canvas.1[1], PlottedCoords(1, 2, Representation::Canvas, None));
+        assert_eq!(canvas.1[2], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, None));
+
+        assert_eq!(canvas.1[3], PlottedCoords(0, 1, Representation::Canvas, None));
+        assert_eq!(canvas.1[4], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[5], PlottedCoords(2, 1, Representation::Canvas, None));
+
+        assert_eq!(canvas.1[6], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[7], PlottedCoords(1, 0, Representation::HorizontalLine, None));
+        assert_eq!(canvas.1[8], PlottedCoords(2, 0, Representation::HorizontalLine, None));
+    }
+
+    #[test]
+    fn test_plot_circle() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let circle_1 = fixtures_circle();
+
+        // combined object is supposed to be large enough to contain 2 lines
+        let lines_combined = combine(Box::new(line_1), Box::new(circle_1)).unwrap();
+        let canvas = plot(Box::new(lines_combined)).unwrap();
+
+        assert_eq!((canvas.0).0, 3);
+        assert_eq!((canvas.0).1, 3);
+
+        assert_eq!((canvas.1).len(), 9);
+
+        assert_eq!(canvas.1[0], PlottedCoords(0, 2, Representation::Canvas, None));
+        assert_eq!(canvas.1[1], PlottedCoords(1, 2, Representation::Circle, None));
+        assert_eq!(canvas.1[2], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, None));
+
+        assert_eq!(canvas.1[3], PlottedCoords(0, 1, Representation::Circle, None));
+        assert_eq!(canvas.1[4], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[5], PlottedCoords(2, 1, Representation::Circle, None));
+
+        assert_eq!(canvas.1[6], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight, None));
+        assert_eq!(canvas.1[7], PlottedCoords(1, 0, Representation::Circle, None));
+        assert_eq!(canvas.1[8], PlottedCoords(2, 0, Representation::Canvas, None));
+    }
+
+    #[test]
+    fn test_plot_horizontal_line()
+    {
+        let horizontal_line = fixtures_horizontal_line();
+        let canvas = plot(Box::new(horizontal_line)).unwrap();
+
+        assert_eq!((canvas.0).0, 3);
+        assert_eq!((canvas.0).1, 1);
+
+        assert_eq!((canvas.1).len(), 3);
+
+        assert_eq!(canvas.1[0], PlottedCoords(0, 0, Representation::HorizontalLine, None));
+        assert_eq!(canvas.1[1], PlottedCoords(1, 0, Representation::HorizontalLine, None));
+        assert_eq!(canvas.1[2], PlottedCoords(2, 0, Representation::HorizontalLine, None));
+    }
+
+    #[test]
+    fn test_plot_sparse_only_stores_drawn_cells() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let circle_1 = fixtures_circle();
+
+        let canvas = plot_sparse(Box::new(combine(Box::new(line_1), Box::new(circle_1)).unwrap())).unwrap();
+
+        assert_eq!(canvas.0, Dimensions(3, 3));
+        assert_eq!(canvas.1, vec![
+            PlottedCoords(1, 2, Representation::Circle, None),
+            PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, None),
+            PlottedCoords(0, 1, Representation::Circle, None),
+            PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight, None),
+            PlottedCoords(2, 1, Representation::Circle, None),
+            PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight, None),
+            PlottedCoords(1, 0, Representation::Circle, None),
+        ]);
+
+        assert_eq!(Representation::Circle, canvas.get_representation_at(Coords(1, 2)));
+        assert_eq!(Representation::Canvas, canvas.get_representation_at(Coords(0, 2)));
+    }
+
+    #[test]
+    fn test_plot_translates_negative_space() {
+        let circle = Circle::new(Coords(0, 0), 1).unwrap();
+        let line = Line::new(Coords(-1, -1), Coords(1, 1)).unwrap();
+
+        let canvas = plot(Box::new(combine(Box::new(circle), Box::new(line)).unwrap())).unwrap();
+
+        assert_eq!(canvas.0, Dimensions(3, 3));
+        assert_eq!("  o /\no / o\n/ o  \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_plot_errors() {
+        let empty = CombinedObject(Dimensions(0, 3), vec![]);
+        assert_eq!(Err(Error::EmptyShape), plot(Box::new(empty)).map(|_| ()));
+
+        let huge = CombinedObject(Dimensions(1 << 20, 1 << 20), vec![]);
+        assert_eq!(Err(Error::CanvasTooLarge(Dimensions(1 << 20, 1 << 20))), plot(Box::new(huge)).map(|_| ()));
+
+        let huge = CombinedObject(Dimensions(1 << 20, 1 << 20), vec![Box::new(fixtures_circle())]);
+        assert_eq!(4, plot_sparse(Box::new(huge)).unwrap().1.len());
+    }
+
+    #[test]
+    fn test_flood_fill_inside_outline() {
+        for plot_fn in [plot, plot_sparse] {
+            let mut canvas = plot_fn(Box::new(Rectangle::new(Coords(0, 0), Coords(4, 3)).unwrap())).unwrap();
+
+            assert_eq!(Ok(6), flood_fill(&mut canvas, Coords(2, 1), '#', Connectivity::Four, 100));
+            assert_eq!("+ - - - +\n| # # # |\n| # # # |\n+ - - - +\n", render(&canvas, &Charset::ascii()));
+
+            // filled cells are not empty anymore
+            assert_eq!(Ok(0), flood_fill(&mut canvas, Coords(1, 1), '#', Connectivity::Four, 100));
+        }
+    }
+
+    #[test]
+    fn test_flood_fill_regions_with_different_glyphs() {
+        let left = Rectangle::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let right = Rectangle::new(Coords(2, 0), Coords(4, 2)).unwrap();
+        let mut canvas = plot(Box::new(combine(Box::new(left), Box::new(right)).unwrap())).unwrap();
+
+        assert_eq!(Ok(1), flood_fill(&mut canvas, Coords(1, 1), '#', Connectivity::Four, 100));
+        assert_eq!(Ok(1), flood_fill(&mut canvas, Coords(3, 1), '.', Connectivity::Four, 100));
+
+        assert_eq!(Representation::Fill, canvas.get_representation_at(Coords(3, 1)));
+        assert_eq!("+ - + - +\n| # | . |\n+ - + - +\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_flood_fill_connectivity() {
+        // a diagonal line only stops the fill if it can't spread diagonally
+        let line = Line::new(Coords(0, 2), Coords(2, 0)).unwrap();
+
+        let mut canvas = plot(Box::new(line)).unwrap();
+        assert_eq!(Ok(3), flood_fill(&mut canvas, Coords(0, 0), '#', Connectivity::Four, 100));
+
+        let line = Line::new(Coords(0, 2), Coords(2, 0)).unwrap();
+
+        let mut canvas = plot_sparse(Box::new(line)).unwrap();
+        assert_eq!(Ok(6), flood_fill(&mut canvas, Coords(0, 0), '#', Connectivity::Eight, 100));
+        assert_eq!("\\ # #\n# \\ #\n# # \\\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_flood_fill_budget_on_large_sparse_canvas() {
+        let mut canvas = plot_sparse(Box::new(Line::new(Coords(0, 0), Coords(100_000, 100_000)).unwrap())).unwrap();
+        let cells = canvas.1.len();
+
+        assert_eq!(Err(Error::FillTooLarge(10_000)), flood_fill(&mut canvas, Coords(0, 5), '#', Connectivity::Four, 10_000));
+        assert_eq!(cells, canvas.1.len());
+
+        // a fill of exactly max_cells cells is fine
+        let mut canvas = plot_sparse(Box::new(Rectangle::new(Coords(0, 0), Coords(4, 3)).unwrap())).unwrap();
+
+        assert_eq!(Err(Error::FillTooLarge(5)), flood_fill(&mut canvas, Coords(2, 1), '#', Connectivity::Four, 5));
+        assert_eq!(Ok(6), flood_fill(&mut canvas, Coords(2, 1), '#', Connectivity::Four, 6));
+    }
+
+    #[test]
+    fn test_flood_fill_seed_outside() {
+        let mut canvas = plot(Box::new(fixtures_circle())).unwrap();
+
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(3, 0))), flood_fill(&mut canvas, Coords(3, 0), '#', Connectivity::Four, 100));
+    }
+
+    #[test]
+    fn test_render_sparse_and_dense_canvas_alike() {
+        let dense = plot(Box::new(combine(Box::new(fixtures_vertical_line()), Box::new(fixtures_circle())).unwrap())).unwrap();
+        let sparse = plot_sparse(Box::new(combine(Box::new(fixtures_vertical_line()), Box::new(fixtures_circle())).unwrap())).unwrap();
+
+        assert_eq!(render(&dense, &Charset::ascii()), render(&sparse, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_keeps_spacing_and_row_order() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let circle_1 = fixtures_circle();
+
+        let canvas = plot(Box::new(combine(Box::new(line_1), Box::new(circle_1)).unwrap())).unwrap();
+
+        assert_eq!("  o /\no / o\n/ o  \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_to_writer_with_custom_glyphs() {
+        let canvas = plot(Box::new(fixtures_horizontal_line())).unwrap();
+        let charset = Charset::ascii().with_glyph(Representation::HorizontalLine, '=');
+
+        let mut output = vec![];
+        render_to(&canvas, &charset, &mut output).unwrap();
+
+        assert_eq!(b"= = =\n".to_vec(), output);
+    }
+
+    #[test]
+    fn test_render_box_drawing_charsets() {
+        let rectangle = Rectangle::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let canvas = plot(Box::new(rectangle)).unwrap();
+
+        assert_eq!("┏ ━ ┓\n┃   ┃\n┗ ━ ┛\n", render(&canvas, &Charset::heavy()));
+        assert_eq!("╔ ═ ╗\n║   ║\n╚ ═ ╝\n", render(&canvas, &Charset::double()));
+        assert_eq!("╭ ─ ╮\n│   │\n╰ ─ ╯\n", render(&canvas, &Charset::rounded()));
+        assert_eq!(Some(Charset::light()), Charset::from_name("unicode"));
+        assert_eq!(None, Charset::from_name("bold"));
+    }
+
+    #[test]
+    fn test_render_connects_lines_next_to_each_other() {
+        let vertical = Line::new(Coords(1, 0), Coords(1, 1)).unwrap();
+        let horizontal = Line::new(Coords(0, 2), Coords(2, 2)).unwrap();
+        let canvas = plot(Box::new(combine(Box::new(vertical), Box::new(horizontal)).unwrap())).unwrap();
+
+        assert_eq!("━ ┳ ━\n  ┃  \n  ┃  \n", render(&canvas, &Charset::heavy()));
+        assert_eq!("- - -\n  |  \n  |  \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let red = Style::new().foreground(Color::Red);
+        let line = Styled::new(Line::new(Coords(0, 0), Coords(1, 0)).unwrap(), red);
+        let canvas = plot(Box::new(combine(Box::new(line), Box::new(Line::new(Coords(0, 1), Coords(1, 1)).unwrap())).unwrap())).unwrap();
+
+        assert_eq!("- -\n\x1b[31m-\x1b[0m \x1b[31m-\x1b[0m\n", render_ansi(&canvas, &Charset::ascii()));
+        assert_eq!("- -\n- -\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_ansi_trimmed() {
+        let red = Style::new().foreground(Color::Red);
+        let line = Styled::new(Line::new(Coords(0, 0), Coords(1, 0)).unwrap(), red);
+        let canvas = plot(Box::new(combine(Box::new(line), Box::new(Line::new(Coords(0, 1), Coords(0, 1)).unwrap())).unwrap())).unwrap();
+
+        // the padding of the last cell is trimmed inside its escape sequences, the empty cell after the line is dropped
+        let mut output = vec![];
+        render_ansi_with_to(&canvas, &Charset::ascii(), &RenderOptions::new().stretch(2).trim(), &mut output).unwrap();
+
+        assert_eq!("-\n\x1b[31m--\x1b[0m \x1b[31m-\x1b[0m\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn test_render_glyph_per_object() {
+        let stars = Styled::new(Circle::new(Coords(1, 1), 1).unwrap(), Style::new().glyph('*'));
+        let line = Styled::new(Line::new(Coords(0, 3), Coords(2, 3)).unwrap(), Style::new().glyph('='));
+        let canvas = plot(Box::new(combine(Box::new(stars), Box::new(line)).unwrap())).unwrap();
+
+        assert_eq!("= = =\n  *  \n*   *\n  *  \n", render(&canvas, &Charset::unicode()));
+
+        // the charset of the object is used for its junctions too
+        let left = Rectangle::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let right = Rectangle::new(Coords(2, 0), Coords(4, 2)).unwrap();
+        let grid = Styled::new(combine(Box::new(left), Box::new(right)).unwrap(), Style::new().glyphs(Charset::heavy()));
+        let canvas = plot(Box::new(grid)).unwrap();
+
+        assert_eq!("┏ ━ ┳ ━ ┓\n┃   ┃   ┃\n┗ ━ ┻ ━ ┛\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_with_options() {
+        let canvas = plot(Box::new(Rectangle::new(Coords(0, 0), Coords(2, 1)).unwrap())).unwrap();
+
+        assert_eq!(render(&canvas, &Charset::ascii()), render_with(&canvas, &Charset::ascii(), &RenderOptions::new()));
+        assert_eq!("+-+\n+-+\n", render_with(&canvas, &Charset::ascii(), &RenderOptions::new().separator("")));
+        assert_eq!("+|-|+\n+|-|+\n", render_with(&canvas, &Charset::ascii(), &RenderOptions::new().separator("|")));
+        assert_eq!("┌───┐\n└───┘\n", render_with(&canvas, &Charset::unicode(), &RenderOptions::new().separator("").stretch(2).trim()));
+    }
+
+    #[test]
+    fn test_render_options_aspect_ratio() {
+        assert_eq!(1.0, RenderOptions::new().get_aspect_ratio());
+        assert_eq!(2.0, RenderOptions::new().separator("").get_aspect_ratio());
+        assert_eq!(0.5, RenderOptions::new().separator(" | ").stretch(1).get_aspect_ratio());
+        assert_eq!(1.0, RenderOptions::new().separator("").stretch(2).get_aspect_ratio());
+    }
+
+    #[test]
+    fn test_render_stretched() {
+        let line = Line::new(Coords(0, 0), Coords(1, 1)).unwrap();
+        let canvas = plot(Box::new(line)).unwrap();
+
+        // cells without a line to the right are padded with spaces, trimming removes them at the end of a row
+        assert_eq!("     /   \n/        \n", render_with(&canvas, &Charset::ascii(), &RenderOptions::new().stretch(4)));
+        assert_eq!("     /\n/\n", render_with(&canvas, &Charset::ascii(), &RenderOptions::new().stretch(4).trim()));
+        assert_eq!(render(&canvas, &Charset::ascii()), render_with(&canvas, &Charset::ascii(), &RenderOptions::new().stretch(0)));
+
+        // the last cell of a line doesn't continue it
+        let line = plot(Box::new(Line::new(Coords(0, 0), Coords(2, 0)).unwrap())).unwrap();
+        assert_eq!("-------  \n", render_with(&line, &Charset::ascii(), &RenderOptions::new().separator("").stretch(3)));
+        assert_eq!("-------\n", render_with(&line, &Charset::ascii(), &RenderOptions::new().separator("").stretch(3).trim()));
+
+        let filled = plot(Box::new(Rectangle::new(Coords(0, 0), Coords(2, 2)).unwrap().filled())).unwrap();
+        assert_eq!("+---+\n| ##|\n+---+\n", render_with(&filled, &Charset::ascii(), &RenderOptions::new().separator("").stretch(2).trim()));
+    }
+
+    #[test]
+    fn test_plot_in_screen_coordinates() {
+        let line = Line::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let canvas = plot_in(Box::new(line), CoordinateSystem::Screen).unwrap();
+
+        // y = 0 is the top row and the glyphs are flipped with the drawing
+        assert_eq!("\\    \n  \\  \n    \\\n", render(&canvas, &Charset::ascii()));
+        assert_eq!(Representation::DiagonalLineRightToLeft, canvas.get_representation_at(Coords(0, 0)));
+        assert_eq!(PlottedCoords(0, 0, Representation::DiagonalLineRightToLeft, None), canvas.1[0]);
+    }
+
+    #[test]
+    fn test_screen_coordinates_keep_junctions_and_corners() {
+        // a rounded box with a stem below it, drawn in both coordinate systems
+        let drawing = |box_y, stem_y| {
+            let rectangle = Rectangle::new(Coords(0, box_y), Coords(4, box_y + 2)).unwrap().rounded();
+            Box::new(combine(Box::new(rectangle), Box::new(Line::new(Coords(2, stem_y), Coords(2, stem_y + 1)).unwrap())).unwrap())
+        };
+
+        let cartesian = render(&plot(drawing(1, 0)).unwrap(), &Charset::unicode());
+
+        assert_eq!("╭ ─ ─ ─ ╮\n│       │\n╰ ─ ┬ ─ ╯\n    │    \n", cartesian);
+        assert_eq!(cartesian, render(&plot_in(drawing(0, 2), CoordinateSystem::Screen).unwrap(), &Charset::unicode()));
+        assert_eq!(cartesian, render(&plot_sparse_in(drawing(0, 2), CoordinateSystem::Screen).unwrap(), &Charset::unicode()));
+    }
+
+    #[test]
+    fn test_flood_fill_in_screen_coordinates() {
+        let rectangle = Rectangle::new(Coords(0, 0), Coords(3, 2)).unwrap();
+        let line = Line::new(Coords(0, 4), Coords(3, 4)).unwrap();
+        let mut canvas = plot_sparse_in(Box::new(combine(Box::new(rectangle), Box::new(line)).unwrap()), CoordinateSystem::Screen).unwrap();
+
+        assert_eq!(Ok(2), flood_fill(&mut canvas, Coords(1, 1), '#', Connectivity::Four, 100));
+        assert_eq!("+ - - +\n| # # |\n+ - - +\n       \n- - - -\n", render(&canvas, &Charset::ascii()));
+    }
+}
@@ -0,0 +1,229 @@
+use coords::{Coords, Dimensions, Path, Representation, MAX_COORD, MIN_COORD};
+use ellipse::Ellipse;
+use error::Error;
+use fill::Filling;
+use plottable::Plottable;
+use raster::Raster;
+
+#[derive(Debug)]
+pub struct Circle(Dimensions, Vec<Coords>, Filling);
+
+impl Circle {
+    pub fn new(point: Coords, radius: u32) -> Result<Circle, Error> {
+        let Coords(x0, y0) = point.check_range()?;
+
+        check_radius(x0, radius)?;
+        check_radius(y0, radius)?;
+
+        let mut x = radius as i32;
+        let mut y = 0;
+
+        let mut err = 0;
+
+        let mut coords = vec![];
+
+        while x >= y {
+            coords.push(Coords(x0 + x, y0 + y));
+            coords.push(Coords(x0 + y, y0 + x));
+            coords.push(Coords(x0 - y, y0 + x));
+            coords.push(Coords(x0 - x, y0 + y));
+            coords.push(Coords(x0 - x, y0 - y));
+            coords.push(Coords(x0 - y, y0 - x));
+            coords.push(Coords(x0 + y, y0 - x));
+            coords.push(Coords(x0 + x, y0 - y));
+
+            y += 1;
+            err += 1 + 2 * y;
+
+            if 2 * (err - x) + 1 > 0 {
+                x -= 1;
+                err += 1 - 2 * x;
+            }
+        }
+
+        let coords = Coords::unique(coords);
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Circle(dimensions, coords, Filling::default()))
+    }
+
+    // a circle stretched horizontally by the aspect ratio of a cell (height / width), so it looks round when
+    // the cells aren't square, e.g. RenderOptions::get_aspect_ratio() for the cells rendered as text
+    pub fn with_aspect_ratio(point: Coords, radius: u32, aspect_ratio: f64) -> Result<Circle, Error> {
+        if !aspect_ratio.is_finite() || aspect_ratio <= 0.0 {
+            return Err(Error::InvalidAspectRatio(aspect_ratio));
+        }
+
+        let radius_x = (f64::from(radius) * aspect_ratio).round().max(1.0);
+        let radius_x = if radius_x > f64::from(MAX_COORD) { u32::MAX } else { radius_x as u32 };
+
+        let ellipse = Ellipse::new(point, radius_x, radius)?;
+        Ok(Circle(ellipse.get_dimensions(), ellipse.get_coords(), Filling::default()))
+    }
+
+    // fills the inside with Representation::Fill, a disc
+    pub fn filled(mut self) -> Circle {
+        self.2 = Filling::new(&get_outline_path(&self.1), &self.1);
+        self
+    }
+}
+
+impl Plottable for Circle {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.1)
+    }
+
+    // the outline followed by the filling
+    fn get_coords(&self) -> Vec<Coords> {
+        let mut coords = self.1.clone();
+        coords.extend_from_slice(self.2.get_coords());
+        coords
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        if self.2.contains(coords) { Some(Representation::Fill) } else { Some(Representation::Circle) }
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        let mut paths = vec![Path(Representation::Circle, get_outline_path(&self.1))];
+        paths.extend_from_slice(self.2.get_paths());
+        paths
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.1.iter() {
+            raster.set(*coords, Representation::Circle);
+        }
+
+        for coords in self.2.get_coords() {
+            raster.set(*coords, Representation::Fill);
+        }
+    }
+}
+
+// -- functions --
+// the characters of a terminal are about twice as tall as wide. a cell is rendered as one or more characters,
+// see RenderOptions::get_aspect_ratio()
+pub const TERMINAL_CELL_ASPECT_RATIO: f64 = 2.0;
+
+// the outline ordered by angle around the center, closed by repeating the first coords
+pub(crate) fn get_outline_path(coords: &[Coords]) -> Vec<Coords> {
+    let min_x = coords.iter().map(|a| a.0).min().unwrap();
+    let max_x = coords.iter().map(|a| a.0).max().unwrap();
+    let min_y = coords.iter().map(|a| a.1).min().unwrap();
+    let max_y = coords.iter().map(|a| a.1).max().unwrap();
+
+    let center_x = (f64::from(min_x) + f64::from(max_x)) / 2.0;
+    let center_y = (f64::from(min_y) + f64::from(max_y)) / 2.0;
+    let angle = |&Coords(x, y): &Coords| (f64::from(y) - center_y).atan2(f64::from(x) - center_x);
+
+    let mut path = coords.to_vec();
+    path.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap());
+    path.push(path[0]);
+
+    path
+}
+
+// the radius has to fit around the center on both sides. the margins are calculated in i64 as a center at the
+// edge of the range is twice as far from the other edge as fits into i32
+pub(crate) fn check_radius(center: i32, radius: u32) -> Result<(), Error> {
+    let (center, distance) = (i64::from(center), i64::from(radius));
+
+    if radius == 0 || center - i64::from(MIN_COORD) < distance || i64::from(MAX_COORD) - center < distance {
+        return Err(Error::InvalidRadius(radius));
+    }
+
+    Ok(())
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, render_with, Charset, RenderOptions};
+
+    #[test]
+    fn test_new_circle() {
+        let circle = Circle::new(Coords(1, 1), 1).unwrap();
+
+        assert_eq!(Dimensions(3, 3), circle.get_dimensions());
+        assert_eq!(vec![Coords(2, 1), Coords(1, 2), Coords(0, 1), Coords(1, 0)], circle.get_coords());
+    }
+
+    #[test]
+    fn test_new_circle_has_unique_coords() {
+        let circle = Circle::new(Coords(5, 5), 4).unwrap();
+        let coords = circle.get_coords();
+
+        assert_eq!(coords, Coords::unique(coords.clone()));
+    }
+
+    #[test]
+    fn test_circle_path_is_closed_outline() {
+        let circle = Circle::new(Coords(1, 1), 1).unwrap();
+
+        assert_eq!(vec![Path(Representation::Circle, vec![Coords(1, 0), Coords(2, 1), Coords(1, 2), Coords(0, 1), Coords(1, 0)])], circle.get_paths());
+    }
+
+    #[test]
+    fn test_filled_circle() {
+        let circle = Circle::new(Coords(2, 2), 2).unwrap().filled();
+
+        assert_eq!(Some(Representation::Fill), circle.get_representation_at(Coords(2, 2)));
+        assert_eq!(Some(Representation::Circle), circle.get_representation_at(Coords(0, 2)));
+
+        let canvas = plot(Box::new(circle)).unwrap();
+        assert_eq!("    o    \n  o # o  \no # # # o\n  o # o  \n    o    \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_new_circle_invalid_radius() {
+        assert_eq!(Err(Error::InvalidRadius(0)), Circle::new(Coords(1, 1), 0).map(|_| ()));
+        assert_eq!(Err(Error::InvalidRadius(3)), Circle::new(Coords(MAX_COORD - 2, 0), 3).map(|_| ()));
+        assert_eq!(Err(Error::InvalidRadius(u32::MAX)), Circle::new(Coords(0, 0), u32::MAX).map(|_| ()));
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(0, MIN_COORD - 1))), Circle::new(Coords(0, MIN_COORD - 1), 1).map(|_| ()));
+    }
+
+    #[test]
+    fn test_new_circle_at_edge_of_range() {
+        assert_eq!(Err(Error::InvalidRadius(1)), Circle::new(Coords(MAX_COORD, 0), 1).map(|_| ()));
+        assert_eq!(Err(Error::InvalidRadius(1)), Circle::new(Coords(0, MIN_COORD), 1).map(|_| ()));
+        assert_eq!(Err(Error::InvalidRadius(u32::MAX)), Circle::new(Coords(MIN_COORD, MAX_COORD), u32::MAX).map(|_| ()));
+        assert_eq!(4, Circle::new(Coords(MAX_COORD - 1, MIN_COORD + 1), 1).unwrap().get_coords().len());
+    }
+
+    #[test]
+    fn test_circle_with_aspect_ratio() {
+        let circle = Circle::with_aspect_ratio(Coords(0, 0), 2, TERMINAL_CELL_ASPECT_RATIO).unwrap();
+
+        assert_eq!(Dimensions(9, 5), circle.get_dimensions());
+        assert_eq!(Coords(-4, -2), circle.get_origin());
+        assert_eq!(Err(Error::InvalidAspectRatio(0.0)), Circle::with_aspect_ratio(Coords(0, 0), 2, 0.0).map(|_| ()));
+    }
+
+    #[test]
+    fn test_circle_with_aspect_ratio_of_render_options_looks_round() {
+        let width = |options: RenderOptions| {
+            let circle = Circle::with_aspect_ratio(Coords(0, 0), 3, options.get_aspect_ratio()).unwrap();
+            let output = render_with(&plot(Box::new(circle)).unwrap(), &Charset::ascii(), &options.trim());
+
+            (output.lines().map(|line| line.chars().count()).max().unwrap(), output.lines().count())
+        };
+
+        // 7 rows of characters twice as tall as wide look as wide as 13 or 14 characters
+        assert_eq!((13, 7), width(RenderOptions::new()));
+        assert_eq!((13, 7), width(RenderOptions::new().separator("")));
+        assert_eq!((13, 7), width(RenderOptions::new().separator("").stretch(2)));
+    }
+
+    #[test]
+    fn test_new_circle_near_origin() {
+        let circle = Circle::new(Coords(0, 1), 2).unwrap();
+
+        assert_eq!(Coords(-2, -1), circle.get_origin());
+        assert_eq!(Dimensions(5, 5), circle.get_dimensions());
+    }
+}
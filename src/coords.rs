@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use error::Error;
+use style::Style;
+
+// coordinates are limited so the dimensions of any object fit into u32. the difference between two coords can
+// still exceed i32 and has to be calculated in i64 or f64
+pub const MIN_COORD: i32 = -(1 << 30);
+pub const MAX_COORD: i32 = 1 << 30;
+
+// -- structs --
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Representation {
+    Canvas,
+    VerticalLine,
+    HorizontalLine,
+    DiagonalLineLeftToRight,
+    DiagonalLineRightToLeft,
+    Circle,
+    TopLeftCorner,
+    TopRightCorner,
+    BottomLeftCorner,
+    BottomRightCorner,
+    RoundedTopLeftCorner,
+    RoundedTopRightCorner,
+    RoundedBottomLeftCorner,
+    RoundedBottomRightCorner,
+    Vertex,
+    Fill,
+    // junctions where lines meet, a tee is named after the direction of its stem
+    Cross,
+    TeeUp,
+    TeeDown,
+    TeeLeft,
+    TeeRight,
+}
+
+impl Representation {
+    // the direction of a line drawn with this representation, None for anything but lines
+    pub fn get_direction(&self) -> Option<(f64, f64)> {
+        match *self {
+            Representation::HorizontalLine => Some((1.0, 0.0)),
+            Representation::VerticalLine => Some((0.0, 1.0)),
+            Representation::DiagonalLineLeftToRight => Some((1.0, 1.0)),
+            Representation::DiagonalLineRightToLeft => Some((1.0, -1.0)),
+            _ => None,
+        }
+    }
+
+    // the line representation closest to the direction
+    pub fn from_direction(dx: f64, dy: f64) -> Representation {
+        let angle = dy.atan2(dx).to_degrees().rem_euclid(180.0);
+
+        if !(22.5..157.5).contains(&angle) {
+            Representation::HorizontalLine
+        } else if angle < 67.5 {
+            Representation::DiagonalLineLeftToRight
+        } else if angle < 112.5 {
+            Representation::VerticalLine
+        } else {
+            Representation::DiagonalLineRightToLeft
+        }
+    }
+
+    // the direction from a corner towards the inside of the box, None for anything but corners
+    pub fn get_corner_direction(&self) -> Option<(f64, f64)> {
+        match *self {
+            Representation::TopLeftCorner | Representation::RoundedTopLeftCorner => Some((1.0, -1.0)),
+            Representation::TopRightCorner | Representation::RoundedTopRightCorner => Some((-1.0, -1.0)),
+            Representation::BottomLeftCorner | Representation::RoundedBottomLeftCorner => Some((1.0, 1.0)),
+            Representation::BottomRightCorner | Representation::RoundedBottomRightCorner => Some((-1.0, 1.0)),
+            _ => None,
+        }
+    }
+
+    // the corner whose inside is in the direction
+    pub fn corner_from_direction(dx: f64, dy: f64, rounded: bool) -> Representation {
+        match (dx >= 0.0, dy >= 0.0, rounded) {
+            (true, false, false) => Representation::TopLeftCorner,
+            (false, false, false) => Representation::TopRightCorner,
+            (true, true, false) => Representation::BottomLeftCorner,
+            (false, true, false) => Representation::BottomRightCorner,
+            (true, false, true) => Representation::RoundedTopLeftCorner,
+            (false, false, true) => Representation::RoundedTopRightCorner,
+            (true, true, true) => Representation::RoundedBottomLeftCorner,
+            (false, true, true) => Representation::RoundedBottomRightCorner,
+        }
+    }
+
+    pub fn is_rounded_corner(&self) -> bool {
+        matches!(*self, Representation::RoundedTopLeftCorner | Representation::RoundedTopRightCorner
+            | Representation::RoundedBottomLeftCorner | Representation::RoundedBottomRightCorner)
+    }
+
+    // the representation upside down
+    pub fn flip_vertically(&self) -> Representation {
+        if let Some((dx, dy)) = self.get_direction() {
+            return Representation::from_direction(dx, -dy);
+        }
+
+        if let Some((dx, dy)) = self.get_corner_direction() {
+            return Representation::corner_from_direction(dx, -dy, self.is_rounded_corner());
+        }
+
+        match *self {
+            Representation::TeeUp => Representation::TeeDown,
+            Representation::TeeDown => Representation::TeeUp,
+            ref representation => representation.clone(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Coords(pub i32, pub i32);
+
+impl Coords {
+    pub fn check_range(self) -> Result<Coords, Error> {
+        let Coords(x, y) = self;
+
+        let range = MIN_COORD..=MAX_COORD;
+
+        if !range.contains(&x) || !range.contains(&y) {
+            return Err(Error::CoordsOutOfRange(self));
+        }
+
+        Ok(self)
+    }
+
+    // the coords of the cell containing the point
+    pub fn from_point(x: f64, y: f64) -> Result<Coords, Error> {
+        // casting saturates, so anything too large ends up out of range
+        let coords = Coords(x.round() as i32, y.round() as i32);
+
+        if x.is_nan() || y.is_nan() {
+            return Err(Error::CoordsOutOfRange(coords));
+        }
+
+        coords.check_range()
+    }
+
+    // removes duplicates, keeping the first occurrence so the order of the coords is retained
+    pub fn unique(coords: Vec<Coords>) -> Vec<Coords> {
+        let mut seen = HashSet::with_capacity(coords.len());
+        coords.into_iter().filter(|coords| seen.insert(*coords)).collect()
+    }
+
+    // the bottom left corner of the canvas needed for the coords, which is (0, 0) unless
+    // some coords are in negative space
+    pub fn origin_from_coords(coords: &[Coords]) -> Coords {
+        let x = coords.iter().map(|a| a.0).min().unwrap_or(0);
+        let y = coords.iter().map(|a| a.1).min().unwrap_or(0);
+
+        Coords(x.min(0), y.min(0))
+    }
+
+    pub fn from_dimensions(dimensions: Dimensions) -> Vec<Coords> {
+        fn coords_from_index(i: u32, dimensions: Dimensions) -> Coords {
+            if i < dimensions.0 { Coords(i as i32, 0) }
+            else { Coords((i % dimensions.0) as i32, (i / dimensions.0) as i32) }
+        }
+
+        (0..(dimensions.0 * dimensions.1))
+            .map(|i| { coords_from_index(i, dimensions) })
+            .collect()
+    }
+}
+
+// the style is None for cells drawn without one
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct PlottedCoords(pub u32, pub u32, pub Representation, pub Option<Style>);
+
+// a run of coords drawn by connecting consecutive coords with lines, all in the same representation. a path of
+// Representation::Fill is the closed outline of a filled area instead, the cells inside it are filled
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Path(pub Representation, pub Vec<Coords>);
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Dimensions(pub u32, pub u32);
+
+impl Dimensions {
+    // the canvas size needed for the coords, spanning from Coords::origin_from_coords() to the largest coords.
+    // no coords need no canvas at all
+    pub fn from_coords(coords: &[Coords]) -> Dimensions {
+        if coords.is_empty() {
+            return Dimensions(0, 0);
+        }
+
+        let Coords(origin_x, origin_y) = Coords::origin_from_coords(coords);
+
+        let x = Dimensions::get_max_coord_from_coords(coords.iter(), &|a| a.0);
+        let y = Dimensions::get_max_coord_from_coords(coords.iter(), &|a| a.1);
+
+        Dimensions(
+            (i64::from(x.max(0)) - i64::from(origin_x) + 1) as u32,
+            (i64::from(y.max(0)) - i64::from(origin_y) + 1) as u32,
+        )
+    }
+
+    fn get_max_coord_from_coords<'a, I: Iterator<Item=&'a Coords>>(coords: I, pluck_fn: &dyn Fn(&Coords) -> i32) -> i32 {
+        coords.map(pluck_fn).max().unwrap_or(0)
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_dimension_from_coords() {
+        let coords = vec![Coords(0, 0), Coords(2, 1), Coords(5, 9), Coords(10, 5)];
+        let dimensions = Dimensions::from_coords(&coords);
+
+        assert_eq!(11, dimensions.0);
+        assert_eq!(10, dimensions.1);
+    }
+
+    #[test]
+    fn test_get_dimension_from_coords_in_negative_space() {
+        let coords = vec![Coords(-2, 1), Coords(3, -4)];
+
+        assert_eq!(Coords(-2, -4), Coords::origin_from_coords(&coords));
+        assert_eq!(Dimensions(6, 6), Dimensions::from_coords(&coords));
+
+        let coords = vec![Coords(-3, -3), Coords(-1, -2)];
+
+        assert_eq!(Coords(-3, -3), Coords::origin_from_coords(&coords));
+        assert_eq!(Dimensions(4, 4), Dimensions::from_coords(&coords));
+    }
+
+    #[test]
+    fn test_get_dimension_from_no_coords() {
+        assert_eq!(Dimensions(0, 0), Dimensions::from_coords(&[]));
+        assert_eq!(Coords(0, 0), Coords::origin_from_coords(&[]));
+    }
+
+    #[test]
+    fn test_check_range() {
+        assert_eq!(Ok(Coords(MIN_COORD, MAX_COORD)), Coords(MIN_COORD, MAX_COORD).check_range());
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(0, MAX_COORD + 1))), Coords(0, MAX_COORD + 1).check_range());
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(MIN_COORD - 1, 0))), Coords(MIN_COORD - 1, 0).check_range());
+    }
+
+    #[test]
+    fn test_from_point() {
+        assert_eq!(Ok(Coords(2, -3)), Coords::from_point(1.6, -3.4));
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(i32::MAX, 0))), Coords::from_point(1e12, 0.0));
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(0, 0))), Coords::from_point(0.0, f64::NAN));
+    }
+
+    #[test]
+    fn test_representation_from_direction() {
+        assert_eq!(Representation::HorizontalLine, Representation::from_direction(-5.0, 1.0));
+        assert_eq!(Representation::VerticalLine, Representation::from_direction(0.0, -1.0));
+        assert_eq!(Representation::DiagonalLineLeftToRight, Representation::from_direction(-2.0, -2.5));
+        assert_eq!(Representation::DiagonalLineRightToLeft, Representation::from_direction(3.0, -2.0));
+        assert_eq!(None, Representation::Circle.get_direction());
+    }
+
+    #[test]
+    fn test_representation_corner_from_direction() {
+        assert_eq!(Representation::TopRightCorner, Representation::corner_from_direction(-1.0, -1.0, false));
+        assert_eq!(Representation::RoundedBottomLeftCorner, Representation::corner_from_direction(1.0, 1.0, true));
+        assert_eq!(Some((1.0, -1.0)), Representation::RoundedTopLeftCorner.get_corner_direction());
+        assert_eq!(None, Representation::Fill.get_corner_direction());
+    }
+
+    #[test]
+    fn test_representation_flip_vertically() {
+        assert_eq!(Representation::DiagonalLineRightToLeft, Representation::DiagonalLineLeftToRight.flip_vertically());
+        assert_eq!(Representation::VerticalLine, Representation::VerticalLine.flip_vertically());
+        assert_eq!(Representation::RoundedBottomRightCorner, Representation::RoundedTopRightCorner.flip_vertically());
+        assert_eq!(Representation::TeeDown, Representation::TeeUp.flip_vertically());
+        assert_eq!(Representation::TeeLeft, Representation::TeeLeft.flip_vertically());
+    }
+
+    #[test]
+    fn test_unique_coords_keeps_order() {
+        let coords = vec![Coords(2, 1), Coords(1, 2), Coords(2, 1), Coords(0, 0), Coords(1, 2)];
+
+        assert_eq!(vec![Coords(2, 1), Coords(1, 2), Coords(0, 0)], Coords::unique(coords));
+    }
+}
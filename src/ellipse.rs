@@ -0,0 +1,253 @@
+use circle::{check_radius, get_outline_path, Circle};
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use fill::Filling;
+use plottable::Plottable;
+use raster::Raster;
+
+// -- structs --
+#[derive(Debug)]
+pub struct Ellipse(Dimensions, Vec<Coords>, Filling);
+
+// the part of a circle from the start angle counter-clockwise to the end angle, angles are in degrees
+// with 0 pointing to the right
+#[derive(Debug)]
+pub struct Arc(Dimensions, Vec<Coords>);
+
+impl Ellipse {
+    pub fn new(point: Coords, radius_x: u32, radius_y: u32) -> Result<Ellipse, Error> {
+        let Coords(x0, y0) = point.check_range()?;
+
+        check_radius(x0, radius_x)?;
+        check_radius(y0, radius_y)?;
+
+        // midpoint ellipse algorithm, the decision variables are scaled by 4 to stay integers
+        let (rx2, ry2) = (i128::from(radius_x) * i128::from(radius_x), i128::from(radius_y) * i128::from(radius_y));
+
+        let mut x: i128 = 0;
+        let mut y = i128::from(radius_y);
+
+        let mut coords = vec![];
+        let mut plot = |x: i128, y: i128| {
+            let (x, y) = (x as i32, y as i32);
+
+            coords.push(Coords(x0 + x, y0 + y));
+            coords.push(Coords(x0 - x, y0 + y));
+            coords.push(Coords(x0 - x, y0 - y));
+            coords.push(Coords(x0 + x, y0 - y));
+        };
+
+        // region 1, the slope is flatter than -1
+        let mut err = 4 * ry2 - 4 * rx2 * y + rx2;
+
+        while ry2 * x < rx2 * y {
+            plot(x, y);
+
+            if err >= 0 {
+                y -= 1;
+                err -= 8 * rx2 * y;
+            }
+
+            x += 1;
+            err += 4 * ry2 * (2 * x + 1);
+        }
+
+        // region 2, the slope is steeper than -1
+        err = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+
+        while y >= 0 {
+            plot(x, y);
+
+            if err <= 0 {
+                x += 1;
+                err += 8 * ry2 * x;
+            }
+
+            y -= 1;
+            err += 4 * rx2 * (1 - 2 * y);
+        }
+
+        let coords = Coords::unique(coords);
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Ellipse(dimensions, coords, Filling::default()))
+    }
+
+    pub fn filled(mut self) -> Ellipse {
+        self.2 = Filling::new(&get_outline_path(&self.1), &self.1);
+        self
+    }
+}
+
+impl Arc {
+    pub fn new(point: Coords, radius: u32, start_angle: f64, end_angle: f64) -> Result<Arc, Error> {
+        if !start_angle.is_finite() || !end_angle.is_finite() {
+            return Err(Error::InvalidAngle("the angles have to be finite numbers"));
+        }
+
+        let circle = Circle::new(point, radius)?;
+        let Coords(x0, y0) = point;
+
+        let sweep = if (end_angle - start_angle).abs() >= 360.0 { 360.0 } else { (end_angle - start_angle).rem_euclid(360.0) };
+        let offset = |&Coords(x, y): &Coords| {
+            let angle = f64::from(y - y0).atan2(f64::from(x - x0)).to_degrees();
+            (angle - start_angle).rem_euclid(360.0)
+        };
+
+        let mut coords = circle.get_coords().into_iter().filter(|coords| offset(coords) <= sweep).collect::<Vec<_>>();
+        coords.sort_by(|a, b| offset(a).partial_cmp(&offset(b)).unwrap());
+
+        if coords.is_empty() {
+            return Err(Error::EmptyShape);
+        }
+
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Arc(dimensions, coords))
+    }
+}
+
+impl Plottable for Ellipse {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.1)
+    }
+
+    // the outline followed by the filling
+    fn get_coords(&self) -> Vec<Coords> {
+        let mut coords = self.1.clone();
+        coords.extend_from_slice(self.2.get_coords());
+        coords
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        if self.2.contains(coords) { Some(Representation::Fill) } else { Some(Representation::Circle) }
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        let mut paths = vec![Path(Representation::Circle, get_outline_path(&self.1))];
+        paths.extend_from_slice(self.2.get_paths());
+        paths
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.1.iter() {
+            raster.set(*coords, Representation::Circle);
+        }
+
+        for coords in self.2.get_coords() {
+            raster.set(*coords, Representation::Fill);
+        }
+    }
+}
+
+impl Plottable for Arc {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.1)
+    }
+
+    // ordered from the start angle to the end angle
+    fn get_coords(&self) -> Vec<Coords> {
+        self.1.clone()
+    }
+
+    fn get_representation_at(&self, _: Coords) -> Option<Representation> {
+        Some(Representation::Circle)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        vec![Path(Representation::Circle, self.1.clone())]
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.1.iter() {
+            raster.set(*coords, Representation::Circle);
+        }
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+    use coords::{MAX_COORD, MIN_COORD};
+
+    #[test]
+    fn test_new_ellipse() {
+        let ellipse = Ellipse::new(Coords(4, 2), 4, 2).unwrap();
+        let coords = ellipse.get_coords();
+
+        assert_eq!(Dimensions(9, 5), ellipse.get_dimensions());
+        assert_eq!(coords, Coords::unique(coords.clone()));
+
+        for extreme in [Coords(0, 2), Coords(8, 2), Coords(4, 0), Coords(4, 4)] {
+            assert!(coords.contains(&extreme));
+        }
+    }
+
+    #[test]
+    fn test_render_ellipse() {
+        let canvas = plot(Box::new(Ellipse::new(Coords(3, 1), 3, 1).unwrap())).unwrap();
+
+        assert_eq!("  o o o o o  \no           o\n  o o o o o  \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_filled_ellipse() {
+        let canvas = plot(Box::new(Ellipse::new(Coords(3, 1), 3, 1).unwrap().filled())).unwrap();
+
+        assert_eq!("  o o o o o  \no # # # # # o\n  o o o o o  \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_ellipse_outline_is_symmetric_and_connected() {
+        let coords = Ellipse::new(Coords(0, 0), 7, 3).unwrap().get_coords();
+
+        for &Coords(x, y) in coords.iter() {
+            assert!(coords.contains(&Coords(-x, y)) && coords.contains(&Coords(x, -y)));
+            assert!(coords.iter().filter(|&&Coords(x1, y1)| (x1, y1) != (x, y) && (x1 - x).abs() <= 1 && (y1 - y).abs() <= 1).count() >= 2);
+        }
+    }
+
+    #[test]
+    fn test_new_ellipse_invalid_radius() {
+        assert_eq!(Err(Error::InvalidRadius(0)), Ellipse::new(Coords(0, 0), 3, 0).map(|_| ()));
+    }
+
+    #[test]
+    fn test_new_ellipse_at_edge_of_range() {
+        assert_eq!(Err(Error::InvalidRadius(1)), Ellipse::new(Coords(0, MAX_COORD), 1, 1).map(|_| ()));
+        assert_eq!(Err(Error::InvalidRadius(2)), Ellipse::new(Coords(MIN_COORD, 0), 2, 1).map(|_| ()));
+        assert!(Ellipse::new(Coords(MIN_COORD + 2, MAX_COORD - 1), 2, 1).is_ok());
+    }
+
+    #[test]
+    fn test_new_arc() {
+        let arc = Arc::new(Coords(0, 0), 2, 0.0, 90.0).unwrap();
+        let coords = arc.get_coords();
+
+        assert_eq!(Coords(2, 0), coords[0]);
+        assert_eq!(Coords(0, 2), coords[coords.len() - 1]);
+        assert!(coords.iter().all(|&Coords(x, y)| x >= 0 && y >= 0));
+    }
+
+    #[test]
+    fn test_arc_across_zero_degrees() {
+        let arc = Arc::new(Coords(0, 0), 3, 270.0, 90.0).unwrap();
+
+        assert!(arc.get_coords().iter().all(|&Coords(x, _)| x >= 0));
+        assert_eq!(Coords(0, -3), arc.get_coords()[0]);
+        assert_eq!(Circle::new(Coords(0, 0), 3).unwrap().get_coords().len(), Arc::new(Coords(0, 0), 3, 0.0, 360.0).unwrap().get_coords().len());
+    }
+
+    #[test]
+    fn test_new_arc_invalid_angle() {
+        assert_eq!(Err(Error::InvalidAngle("the angles have to be finite numbers")), Arc::new(Coords(0, 0), 3, 0.0, f64::NAN).map(|_| ()));
+    }
+}
@@ -0,0 +1,37 @@
+use std::error;
+use std::fmt;
+
+use coords::{Coords, Dimensions, MAX_COORD, MIN_COORD};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    InvalidRadius(u32),
+    EmptyShape,
+    CoordsOutOfRange(Coords),
+    UnsupportedRotation(&'static str),
+    InvalidTransform(&'static str),
+    TooFewVertices(usize),
+    InvalidAngle(&'static str),
+    InvalidAspectRatio(f64),
+    CanvasTooLarge(Dimensions),
+    FillTooLarge(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::InvalidRadius(radius) => write!(f, "invalid radius {}, the circle has to be larger than a point and within {} and {}", radius, MIN_COORD, MAX_COORD),
+            Error::EmptyShape => write!(f, "shape does not contain any coords"),
+            Error::CoordsOutOfRange(Coords(x, y)) => write!(f, "coords ({}, {}) are out of range, coordinates have to be within {} and {}", x, y, MIN_COORD, MAX_COORD),
+            Error::UnsupportedRotation(reason) => write!(f, "unsupported rotation, {}", reason),
+            Error::InvalidTransform(reason) => write!(f, "invalid transform, {}", reason),
+            Error::InvalidAngle(reason) => write!(f, "invalid angle, {}", reason),
+            Error::InvalidAspectRatio(aspect_ratio) => write!(f, "invalid aspect ratio {}, it has to be a positive number", aspect_ratio),
+            Error::TooFewVertices(vertices) => write!(f, "too few vertices, a polyline needs at least 2 and a polygon at least 3 distinct vertices but got {}", vertices),
+            Error::CanvasTooLarge(Dimensions(width, height)) => write!(f, "canvas of {}x{} cells is too large to plot", width, height),
+            Error::FillTooLarge(max_cells) => write!(f, "flood fill spreads to more than {} cells", max_cells),
+        }
+    }
+}
+
+impl error::Error for Error {}
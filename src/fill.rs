@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use coords::{Coords, Path, Representation};
+
+// -- structs --
+// the interior of a closed outline, ordered bottom to top, left to right. its path is the closed polygon through
+// the vertices, so it can be filled again after the polygon is transformed
+#[derive(Debug, Default)]
+pub(crate) struct Filling {
+    coords: Vec<Coords>,
+    paths: Vec<Path>,
+}
+
+impl Filling {
+    // fills the polygon through the vertices by the even-odd rule, leaving out the cells of the outline
+    pub(crate) fn new(vertices: &[Coords], outline: &[Coords]) -> Filling {
+        let outline = outline.iter().collect::<HashSet<_>>();
+
+        let mut coords = vec![];
+
+        for (Coords(start, y), Coords(end, _)) in scanline_spans(vertices) {
+            coords.extend((start..=end).map(|x| Coords(x, y)).filter(|coords| !outline.contains(coords)));
+        }
+
+        // spans of a polygon crossing itself can touch
+        coords.dedup();
+
+        let paths = if vertices.len() < 3 { vec![] } else { vec![Path(Representation::Fill, close(vertices))] };
+        Filling { coords, paths }
+    }
+
+    pub(crate) fn get_coords(&self) -> &[Coords] {
+        &self.coords
+    }
+
+    pub(crate) fn get_paths(&self) -> &[Path] {
+        &self.paths
+    }
+
+    pub(crate) fn contains(&self, coords: Coords) -> bool {
+        self.coords.binary_search_by_key(&(coords.1, coords.0), |&Coords(x, y)| (y, x)).is_ok()
+    }
+}
+
+// -- functions --
+// the vertices with the first one repeated at the end, unless it already is
+fn close(vertices: &[Coords]) -> Vec<Coords> {
+    let mut path = vertices.to_vec();
+
+    if path.first() != path.last() {
+        path.push(path[0]);
+    }
+
+    path
+}
+
+// the horizontal spans of cells whose centers are inside the polygon. an edge covers the rows from its
+// lower end up to, but not including, its upper end so vertices shared by two edges are counted once
+fn scanline_spans(vertices: &[Coords]) -> Vec<(Coords, Coords)> {
+    let mut spans = vec![];
+
+    if vertices.len() < 3 {
+        return spans;
+    }
+
+    let min_y = vertices.iter().map(|a| a.1).min().unwrap();
+    let max_y = vertices.iter().map(|a| a.1).max().unwrap();
+
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1)).collect::<Vec<_>>();
+
+    for y in min_y..=max_y {
+        let mut crossings = edges.iter()
+            .filter(|&&(a, b)| (a.1 <= y && y < b.1) || (b.1 <= y && y < a.1))
+            .map(|&(a, b)| {
+                let t = (f64::from(y) - f64::from(a.1)) / (f64::from(b.1) - f64::from(a.1));
+                f64::from(a.0) + t * (f64::from(b.0) - f64::from(a.0))
+            })
+            .collect::<Vec<_>>();
+
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        for pair in crossings.chunks(2).filter(|pair| pair.len() == 2) {
+            let (start, end) = (pair[0].ceil() as i32, pair[1].floor() as i32);
+
+            if start <= end {
+                spans.push((Coords(start, y), Coords(end, y)));
+            }
+        }
+    }
+
+    spans
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanline_spans_of_triangle() {
+        let spans = scanline_spans(&[Coords(0, 0), Coords(4, 0), Coords(0, 4)]);
+
+        assert_eq!(vec![
+            (Coords(0, 0), Coords(4, 0)),
+            (Coords(0, 1), Coords(3, 1)),
+            (Coords(0, 2), Coords(2, 2)),
+            (Coords(0, 3), Coords(1, 3)),
+        ], spans);
+    }
+
+    #[test]
+    fn test_scanline_spans_use_even_odd_rule() {
+        // a bow tie crosses itself in the middle, the spans on both sides are filled
+        let spans = scanline_spans(&[Coords(0, 0), Coords(4, 2), Coords(4, 0), Coords(0, 2)]);
+
+        assert_eq!(vec![(Coords(0, 0), Coords(0, 0)), (Coords(4, 0), Coords(4, 0)), (Coords(0, 1), Coords(2, 1)), (Coords(2, 1), Coords(4, 1))], spans);
+    }
+
+    #[test]
+    fn test_filling_leaves_out_outline() {
+        let vertices = [Coords(0, 0), Coords(2, 0), Coords(2, 2), Coords(0, 2)];
+        let outline = [Coords(0, 0), Coords(1, 0), Coords(2, 0), Coords(2, 1), Coords(2, 2), Coords(1, 2), Coords(0, 2), Coords(0, 1)];
+        let filling = Filling::new(&vertices, &outline);
+
+        assert_eq!(&[Coords(1, 1)], filling.get_coords());
+        assert!(filling.contains(Coords(1, 1)));
+        assert!(!filling.contains(Coords(0, 1)));
+        assert_eq!(&[Path(Representation::Fill, vec![Coords(0, 0), Coords(2, 0), Coords(2, 2), Coords(0, 2), Coords(0, 0)])], filling.get_paths());
+    }
+}
@@ -0,0 +1,19 @@
+use circle::Circle;
+use coords::Coords;
+use line::Line;
+
+pub fn fixtures_diagonal_line_ltr() -> Line {
+    Line::new(Coords(0, 0), Coords(2, 2)).unwrap()
+}
+
+pub fn fixtures_horizontal_line() -> Line {
+    Line::new(Coords(0, 0), Coords(2, 0)).unwrap()
+}
+
+pub fn fixtures_vertical_line() -> Line {
+    Line::new(Coords(0, 0), Coords(0, 3)).unwrap()
+}
+
+pub fn fixtures_circle() -> Circle {
+    Circle::new(Coords(1, 1), 1).unwrap()
+}
@@ -0,0 +1,217 @@
+use std::ops::BitOr;
+
+use coords::{Coords, Representation};
+
+// -- structs --
+// the directions in which the glyph of a cell connects to its neighbours
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub(crate) struct Arms(u8);
+
+const UP: Arms = Arms(1);
+const DOWN: Arms = Arms(2);
+const LEFT: Arms = Arms(4);
+const RIGHT: Arms = Arms(8);
+
+// the arms of every representation in the order they are declared in, looking them up is a lot faster than
+// matching when rasterizing large drawings. only horizontal and vertical lines, corners and junctions have arms
+const ARMS_OF: [(Representation, Arms); 21] = [
+    (Representation::Canvas, Arms(0)),
+    (Representation::VerticalLine, Arms(UP.0 | DOWN.0)),
+    (Representation::HorizontalLine, Arms(LEFT.0 | RIGHT.0)),
+    (Representation::DiagonalLineLeftToRight, Arms(0)),
+    (Representation::DiagonalLineRightToLeft, Arms(0)),
+    (Representation::Circle, Arms(0)),
+    (Representation::TopLeftCorner, Arms(RIGHT.0 | DOWN.0)),
+    (Representation::TopRightCorner, Arms(LEFT.0 | DOWN.0)),
+    (Representation::BottomLeftCorner, Arms(UP.0 | RIGHT.0)),
+    (Representation::BottomRightCorner, Arms(UP.0 | LEFT.0)),
+    (Representation::RoundedTopLeftCorner, Arms(RIGHT.0 | DOWN.0)),
+    (Representation::RoundedTopRightCorner, Arms(LEFT.0 | DOWN.0)),
+    (Representation::RoundedBottomLeftCorner, Arms(UP.0 | RIGHT.0)),
+    (Representation::RoundedBottomRightCorner, Arms(UP.0 | LEFT.0)),
+    (Representation::Vertex, Arms(0)),
+    (Representation::Fill, Arms(0)),
+    (Representation::Cross, Arms(UP.0 | DOWN.0 | LEFT.0 | RIGHT.0)),
+    (Representation::TeeUp, Arms(UP.0 | LEFT.0 | RIGHT.0)),
+    (Representation::TeeDown, Arms(DOWN.0 | LEFT.0 | RIGHT.0)),
+    (Representation::TeeLeft, Arms(LEFT.0 | UP.0 | DOWN.0)),
+    (Representation::TeeRight, Arms(RIGHT.0 | UP.0 | DOWN.0)),
+];
+
+impl Arms {
+    pub(crate) fn of(representation: &Representation) -> Arms {
+        ARMS_OF[representation.clone() as usize].1
+    }
+
+    pub(crate) fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub(crate) fn reaches_left(self) -> bool {
+        self.contains(LEFT)
+    }
+
+    pub(crate) fn reaches_right(self) -> bool {
+        self.contains(RIGHT)
+    }
+
+    fn contains(self, arms: Arms) -> bool {
+        self.0 & arms.0 == arms.0
+    }
+
+    // None for a single arm, which has no glyph of its own
+    fn to_representation(self) -> Option<Representation> {
+        REPRESENTATION_OF[self.0 as usize].clone()
+    }
+}
+
+// the representation of all 16 combinations of arms, indexed by their bits
+const REPRESENTATION_OF: [Option<Representation>; 16] = [
+    None,                                        // none
+    None,                                        // up
+    None,                                        // down
+    Some(Representation::VerticalLine),          // up, down
+    None,                                        // left
+    Some(Representation::BottomRightCorner),     // up, left
+    Some(Representation::TopRightCorner),        // down, left
+    Some(Representation::TeeLeft),               // up, down, left
+    None,                                        // right
+    Some(Representation::BottomLeftCorner),      // up, right
+    Some(Representation::TopLeftCorner),         // down, right
+    Some(Representation::TeeRight),              // up, down, right
+    Some(Representation::HorizontalLine),        // left, right
+    Some(Representation::TeeUp),                 // up, left, right
+    Some(Representation::TeeDown),               // down, left, right
+    Some(Representation::Cross),                 // up, down, left, right
+];
+
+impl BitOr for Arms {
+    type Output = Arms;
+
+    fn bitor(self, other: Arms) -> Arms {
+        Arms(self.0 | other.0)
+    }
+}
+
+// -- functions --
+// the glyph of a cell where the lines of several objects meet. the arms of all of them are joined, arms
+// pointing at a neighbour which doesn't connect back are dropped so a line ending on another line forms a tee
+// instead of a cross. the first representation is kept if the joined arms are just its own
+pub(crate) fn join<F>(coords: Coords, first: &Representation, arms: Arms, arms_at: F) -> Representation
+    where F: Fn(Coords) -> Arms
+{
+    let Coords(x, y) = coords;
+    let neighbours = [(UP, Coords(x, y + 1), DOWN), (DOWN, Coords(x, y - 1), UP), (LEFT, Coords(x - 1, y), RIGHT), (RIGHT, Coords(x + 1, y), LEFT)];
+
+    let connected = neighbours.iter()
+        .filter(|&&(arm, neighbour, opposite)| arms.contains(arm) && arms_at(neighbour).contains(opposite))
+        .fold(Arms(0), |connected, &(arm, _, _)| connected | arm);
+
+    if connected == Arms::of(first) {
+        return first.clone();
+    }
+
+    connected.to_representation().unwrap_or_else(|| first.clone())
+}
+
+// the glyph of a line cell which also connects to every neighbour with an arm pointing back at it, so box-drawing
+// lines ending next to another line touch it. anything without arms is kept
+pub(crate) fn connect<F>(coords: Coords, representation: &Representation, representation_at: F) -> Representation
+    where F: Fn(Coords) -> Representation
+{
+    let own = Arms::of(representation);
+
+    if own.is_empty() {
+        return representation.clone();
+    }
+
+    let Coords(x, y) = coords;
+    let neighbours = [(UP, Coords(x, y + 1), DOWN), (DOWN, Coords(x, y - 1), UP), (LEFT, Coords(x - 1, y), RIGHT), (RIGHT, Coords(x + 1, y), LEFT)];
+
+    let arms = neighbours.iter()
+        .filter(|&&(_, neighbour, opposite)| Arms::of(&representation_at(neighbour)).contains(opposite))
+        .fold(own, |arms, &(arm, _, _)| arms | arm);
+
+    if arms == own {
+        return representation.clone();
+    }
+
+    arms.to_representation().unwrap_or_else(|| representation.clone())
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn join_at(cells: &HashMap<Coords, Representation>, coords: Coords, first: Representation, other: Representation) -> Representation {
+        let arms = Arms::of(&first) | Arms::of(&other);
+        join(coords, &first, arms, |neighbour| cells.get(&neighbour).map(Arms::of).unwrap_or_default())
+    }
+
+    #[test]
+    fn test_join_crossing_lines() {
+        let mut cells = HashMap::new();
+        cells.insert(Coords(0, 1), Representation::HorizontalLine);
+        cells.insert(Coords(2, 1), Representation::HorizontalLine);
+        cells.insert(Coords(1, 0), Representation::VerticalLine);
+        cells.insert(Coords(1, 2), Representation::VerticalLine);
+
+        assert_eq!(Representation::Cross, join_at(&cells, Coords(1, 1), Representation::HorizontalLine, Representation::VerticalLine));
+
+        cells.remove(&Coords(2, 1));
+        assert_eq!(Representation::TeeLeft, join_at(&cells, Coords(1, 1), Representation::HorizontalLine, Representation::VerticalLine));
+
+        cells.remove(&Coords(1, 0));
+        assert_eq!(Representation::BottomRightCorner, join_at(&cells, Coords(1, 1), Representation::HorizontalLine, Representation::VerticalLine));
+    }
+
+    #[test]
+    fn test_join_keeps_first_representation() {
+        let mut cells = HashMap::new();
+        cells.insert(Coords(1, 0), Representation::HorizontalLine);
+        cells.insert(Coords(0, -1), Representation::VerticalLine);
+
+        assert_eq!(Representation::RoundedTopLeftCorner, join_at(&cells, Coords(0, 0), Representation::RoundedTopLeftCorner, Representation::HorizontalLine));
+        assert_eq!(Representation::HorizontalLine, join_at(&HashMap::new(), Coords(0, 0), Representation::HorizontalLine, Representation::VerticalLine));
+    }
+
+    #[test]
+    fn test_connect_to_neighbours() {
+        let mut cells = HashMap::new();
+        cells.insert(Coords(0, 1), Representation::HorizontalLine);
+        cells.insert(Coords(1, 0), Representation::VerticalLine);
+        cells.insert(Coords(1, 1), Representation::VerticalLine);
+        cells.insert(Coords(1, 2), Representation::HorizontalLine);
+
+        let representation_at = |coords| cells.get(&coords).cloned().unwrap_or(Representation::Canvas);
+
+        // the line to the left ends at the vertical line, the one above runs past its end
+        assert_eq!(Representation::TeeLeft, connect(Coords(1, 1), &Representation::VerticalLine, representation_at));
+        assert_eq!(Representation::TeeDown, connect(Coords(1, 2), &Representation::HorizontalLine, representation_at));
+        assert_eq!(Representation::Circle, connect(Coords(1, 1), &Representation::Circle, representation_at));
+    }
+
+    #[test]
+    fn test_arms_of_is_in_declaration_order() {
+        for (i, (representation, _)) in ARMS_OF.iter().enumerate() {
+            assert_eq!(i, representation.clone() as usize);
+        }
+
+        assert_eq!(LEFT | UP | DOWN, Arms::of(&Representation::TeeLeft));
+
+        // every representation with arms is found again from its arms
+        for (representation, arms) in ARMS_OF.iter().filter(|(representation, _)| !representation.is_rounded_corner()) {
+            let expected = if arms.is_empty() { None } else { Some(representation.clone()) };
+            assert_eq!(expected, arms.to_representation());
+        }
+    }
+
+    #[test]
+    fn test_arms_of_representations_without_arms() {
+        assert!(Arms::of(&Representation::Circle).is_empty());
+        assert!(Arms::of(&Representation::DiagonalLineLeftToRight).is_empty());
+        assert_eq!(None, Arms::of(&Representation::Fill).to_representation());
+    }
+}
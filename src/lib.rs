@@ -0,0 +1,38 @@
+mod bezier;
+mod blocks;
+mod braille;
+mod canvas;
+mod circle;
+mod coords;
+mod ellipse;
+mod error;
+mod fill;
+mod junction;
+mod line;
+mod plottable;
+mod polygon;
+mod raster;
+mod rectangle;
+mod scene;
+mod style;
+mod transform;
+
+#[cfg(test)]
+mod fixtures;
+
+pub use bezier::{CubicBezier, QuadraticBezier};
+pub use blocks::{render_half_blocks, render_half_blocks_to, render_quadrants, render_quadrants_to};
+pub use braille::{render_braille, render_braille_to};
+pub use canvas::{draw, draw_with, draw_with_options, flood_fill, plot, plot_in, plot_sparse, plot_sparse_in, render, render_ansi, render_ansi_to, render_ansi_with_to, render_to, render_with, render_with_to, Canvas, Charset, Connectivity, CoordinateSystem, RenderOptions};
+pub use circle::{Circle, TERMINAL_CELL_ASPECT_RATIO};
+pub use coords::{Coords, Dimensions, Path, PlottedCoords, Representation, MAX_COORD, MIN_COORD};
+pub use ellipse::{Arc, Ellipse};
+pub use error::Error;
+pub use line::Line;
+pub use plottable::{combine, CombinedObject, Plottable};
+pub use polygon::{Polygon, Polyline};
+pub use raster::Raster;
+pub use rectangle::Rectangle;
+pub use scene::{parse_scene, parse_scene_with, Scene, SceneError};
+pub use style::{supports_color, Color, Style, Styled, RESET};
+pub use transform::{rotate, transform, Rotated, Transform, Transformed};
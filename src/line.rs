@@ -0,0 +1,341 @@
+use std::iter::Zip;
+use std::slice::Iter;
+
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use plottable::Plottable;
+use raster::Raster;
+
+// -- structs --
+#[derive(Debug)]
+pub struct Line(Dimensions, Vec<Coords>, Vec<Representation>);
+
+impl Line {
+    // the representation closest to the direction of the whole line
+    pub(crate) fn get_line_representation(start: Coords, end: Coords) -> Representation {
+        Representation::from_direction(f64::from(end.0) - f64::from(start.0), f64::from(end.1) - f64::from(start.1))
+    }
+
+    // every cell is drawn with the glyph of the step reaching it, so runs along an axis are drawn with the glyph of
+    // the axis and the steps between them diagonally. the first cell continues like the second
+    fn get_cell_representations(coords: &[Coords]) -> Vec<Representation> {
+        if coords.len() == 1 {
+            return vec![Line::get_line_representation(coords[0], coords[0])];
+        }
+
+        let step = |from: Coords, to: Coords| Line::get_line_representation(from, to);
+
+        let mut representations = vec![step(coords[0], coords[1])];
+        representations.extend(coords.windows(2).map(|pair| step(pair[0], pair[1])));
+        representations
+    }
+
+    // rotates the line by 90 degrees clockwise around the rotation center, see rotate() for arbitrary angles
+    pub fn rotate(line: Line, rotation_center: Coords) -> Result<Line, Error> {
+        let mut coords = line.get_coords();
+        coords.sort_by_key(|&Coords(x, y)|(x, y));
+
+        let Coords(center_x, center_y) = rotation_center;
+        let rotate = |&Coords(x, y): &Coords| {
+            let (dx, dy) = (f64::from(x) - f64::from(center_x), f64::from(y) - f64::from(center_y));
+            Coords::from_point(f64::from(center_x) + dy, f64::from(center_y) - dx)
+        };
+
+        let start = rotate(coords.first().unwrap())?;
+        let end = rotate(coords.last().unwrap())?;
+
+        Line::new(start, end)
+    }
+
+    pub fn new(start: Coords, end: Coords) -> Result<Line, Error> {
+        let coords = Steps::new(start.check_range()?, end.check_range()?).collect::<Vec<_>>();
+
+        let representations = Line::get_cell_representations(&coords);
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Line(dimensions, coords, representations))
+    }
+
+    // the cells from the start to the end with their representation, unlike get_representation_at() without
+    // searching the line for every cell
+    pub(crate) fn get_cells(&self) -> Zip<Iter<'_, Coords>, Iter<'_, Representation>> {
+        self.1.iter().zip(self.2.iter())
+    }
+}
+
+// the cells from the start to the end by Bresenham's algorithm. two coords within the range can be further apart
+// than fits into i32, so the differences are calculated in i64
+struct Steps {
+    x: i64,
+    y: i64,
+    end: (i64, i64),
+    delta: (i64, i64),
+    step: (i64, i64),
+    err: i64,
+    done: bool,
+}
+
+impl Steps {
+    fn new(start: Coords, end: Coords) -> Steps {
+        let (x0, y0, x1, y1) = (i64::from(start.0), i64::from(start.1), i64::from(end.0), i64::from(end.1));
+        let (dx, dy) = ((x1 - x0).abs(), (y1 - y0).abs());
+
+        Steps {
+            x: x0,
+            y: y0,
+            end: (x1, y1),
+            delta: (dx, dy),
+            step: (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 }),
+            err: (if dx > dy { dx } else { -dy }) / 2,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Steps {
+    type Item = Coords;
+
+    fn next(&mut self) -> Option<Coords> {
+        if self.done {
+            return None;
+        }
+
+        let coords = Coords(self.x as i32, self.y as i32);
+
+        if (self.x, self.y) == self.end {
+            self.done = true;
+            return Some(coords);
+        }
+
+        let (dx, dy) = self.delta;
+        let e2 = self.err;
+
+        if e2 > -dx {
+            self.err -= dy;
+            self.x += self.step.0;
+        }
+
+        if e2 < dy {
+            self.err += dx;
+            self.y += self.step.1;
+        }
+
+        Some(coords)
+    }
+
+    // every step advances along the longer axis
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = if self.done { 0 } else { ((self.end.0 - self.x).abs().max((self.end.1 - self.y).abs()) + 1) as usize };
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Steps {}
+
+impl Plottable for Line {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.1)
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.1.clone()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.1.iter().position(|&line_coords| line_coords == coords).map(|i| self.2[i].clone())
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        let start = self.1[0];
+        let end = self.1[self.1.len() - 1];
+
+        vec![Path(Line::get_line_representation(start, end), vec![start, end])]
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for (coords, representation) in self.get_cells() {
+            raster.set(*coords, representation.clone());
+        }
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+    use coords::{MAX_COORD, MIN_COORD};
+
+    #[test]
+    fn test_rotate_line_vertical_to_horizontal()
+    {
+        let line = Line::new(Coords(5, 5), Coords(5, 9)).unwrap();
+        let rotated_line = Line::rotate(line, Coords(5, 5)).unwrap();
+
+        assert_eq!(vec![Coords(5, 5), Coords(6, 5), Coords(7, 5), Coords(8, 5), Coords(9, 5)], rotated_line.get_coords());
+    }
+
+    #[test]
+    fn test_rotate_line_horizontal_to_vertical()
+    {
+        let line = Line::new(Coords(5, 5), Coords(9, 5)).unwrap();
+        let rotated_line = Line::rotate(line, Coords(5, 5)).unwrap();
+
+        assert_eq!(vec![Coords(5, 5), Coords(5, 4), Coords(5, 3), Coords(5, 2), Coords(5, 1)], rotated_line.get_coords());
+    }
+
+    #[test]
+    fn test_rotate_line_into_negative_space()
+    {
+        let line = Line::new(Coords(1, 1), Coords(4, 1)).unwrap();
+        let rotated_line = Line::rotate(line, Coords(1, 1)).unwrap();
+
+        assert_eq!(vec![Coords(1, 1), Coords(1, 0), Coords(1, -1), Coords(1, -2)], rotated_line.get_coords());
+        assert_eq!(Coords(0, -2), rotated_line.get_origin());
+        assert_eq!(Dimensions(2, 4), rotated_line.get_dimensions());
+    }
+
+    #[test]
+    fn test_rotate_line_around_any_center()
+    {
+        let line = Line::new(Coords(5, 5), Coords(9, 5)).unwrap();
+        let rotated_line = Line::rotate(line, Coords(9, 5)).unwrap();
+
+        assert_eq!(vec![Coords(9, 9), Coords(9, 8), Coords(9, 7), Coords(9, 6), Coords(9, 5)], rotated_line.get_coords());
+
+        let line = Line::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let rotated_line = Line::rotate(line, Coords(1, 0)).unwrap();
+
+        assert_eq!(vec![Coords(1, 1), Coords(2, 0), Coords(3, -1)], rotated_line.get_coords());
+        assert_eq!(Some(Representation::DiagonalLineRightToLeft), rotated_line.get_representation_at(Coords(2, 0)));
+    }
+
+    #[test]
+    fn test_rotate_line_out_of_range()
+    {
+        let line = Line::new(Coords(0, MIN_COORD + 1), Coords(2, MIN_COORD + 1)).unwrap();
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(0, MIN_COORD - 1))), Line::rotate(line, Coords(0, MIN_COORD + 1)).map(|_| ()));
+    }
+
+    #[test]
+    fn test_line_between_extremes_of_range() {
+        // the whole line has 2^31 + 1 cells, too many to allocate in a test
+        let steps = Steps::new(Coords(MIN_COORD, 0), Coords(MAX_COORD, 0));
+        assert_eq!((1 << 31) + 1, steps.len());
+        assert_eq!(vec![Coords(MIN_COORD, 0), Coords(MIN_COORD + 1, 0)], steps.take(2).collect::<Vec<_>>());
+
+        let steps = Steps::new(Coords(MAX_COORD, MAX_COORD), Coords(MIN_COORD, MIN_COORD));
+        assert_eq!(vec![Coords(MAX_COORD, MAX_COORD), Coords(MAX_COORD - 1, MAX_COORD - 1)], steps.take(2).collect::<Vec<_>>());
+
+        assert_eq!(Representation::DiagonalLineRightToLeft, Line::get_line_representation(Coords(MIN_COORD, MAX_COORD), Coords(MAX_COORD, MIN_COORD)));
+
+        let line = Line::new(Coords(MIN_COORD, MAX_COORD), Coords(MIN_COORD + 2, MAX_COORD)).unwrap();
+        assert_eq!(vec![Coords(MIN_COORD, MAX_COORD), Coords(MIN_COORD + 1, MAX_COORD), Coords(MIN_COORD + 2, MAX_COORD)], line.get_coords());
+    }
+
+    #[test]
+    fn test_new_line_out_of_range() {
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(MAX_COORD + 1, 0))), Line::new(Coords(0, 0), Coords(MAX_COORD + 1, 0)).map(|_| ()));
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(i32::MIN, 0))), Line::new(Coords(i32::MIN, 0), Coords(0, 0)).map(|_| ()));
+    }
+
+    #[test]
+    fn test_new_horizontal_line_1() {
+        let line = Line::new(Coords(0, 0), Coords(3, 0)).unwrap();
+
+        assert_eq!(Dimensions(4, 1), line.get_dimensions());
+        assert_eq!(vec![Coords(0, 0), Coords(1, 0), Coords(2, 0), Coords(3, 0)], line.get_coords());
+
+        assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(0, 0)).unwrap());
+        assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(1, 0)).unwrap());
+        assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(2, 0)).unwrap());
+        assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(3, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_new_horizontal_line_2() {
+        let line = Line::new(Coords(1, 1), Coords(3, 1)).unwrap();
+
+        assert_eq!(Dimensions(4, 2), line.get_dimensions());
+        assert_eq!(vec![Coords(1, 1), Coords(2, 1), Coords(3, 1)], line.get_coords());
+    }
+
+    #[test]
+    fn test_new_line_in_negative_space() {
+        let line = Line::new(Coords(-2, -1), Coords(1, -1)).unwrap();
+
+        assert_eq!(Dimensions(4, 2), line.get_dimensions());
+        assert_eq!(Coords(-2, -1), line.get_origin());
+        assert_eq!(vec![Coords(-2, -1), Coords(-1, -1), Coords(0, -1), Coords(1, -1)], line.get_coords());
+    }
+
+    #[test]
+    fn test_new_vertical_line() {
+        let line = Line::new(Coords(0, 0), Coords(0, 3)).unwrap();
+
+        assert_eq!(Dimensions(1, 4), line.get_dimensions());
+        assert_eq!(vec![Coords(0, 0), Coords(0, 1), Coords(0, 2), Coords(0, 3)], line.get_coords());
+
+        assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 0)).unwrap());
+        assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 1)).unwrap());
+        assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 2)).unwrap());
+        assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 3)).unwrap());
+    }
+
+    #[test]
+    fn test_new_diagonal_line_left_to_right() {
+        let line = Line::new(Coords(0, 0), Coords(3, 3)).unwrap();
+
+        assert_eq!(Dimensions(4, 4), line.get_dimensions());
+        assert_eq!(vec![Coords(0, 0), Coords(1, 1), Coords(2, 2),  Coords(3, 3)], line.get_coords());
+
+        assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(0, 0)).unwrap());
+        assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(1, 1)).unwrap());
+        assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(2, 2)).unwrap());
+        assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(3, 3)).unwrap());
+    }
+
+    #[test]
+    fn test_new_diagonal_line_right_to_left() {
+        let line = Line::new(Coords(0, 3), Coords(3, 0)).unwrap();
+
+        assert_eq!(Dimensions(4, 4), line.get_dimensions());
+        assert_eq!(vec![Coords(0, 3), Coords(1, 2), Coords(2, 1),  Coords(3, 0)], line.get_coords());
+
+        assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(0, 3)).unwrap());
+        assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(1, 2)).unwrap());
+        assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(2, 1)).unwrap());
+        assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(3, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_shallow_line_steps_diagonally() {
+        let line = Line::new(Coords(0, 0), Coords(10, 2)).unwrap();
+        let representations = line.get_coords().into_iter().map(|coords| line.get_representation_at(coords).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(2, representations.iter().filter(|&representation| *representation == Representation::DiagonalLineLeftToRight).count());
+        assert_eq!(9, representations.iter().filter(|&representation| *representation == Representation::HorizontalLine).count());
+        assert_eq!(None, line.get_representation_at(Coords(0, 1)));
+
+        let canvas = plot(Box::new(line)).unwrap();
+        assert_eq!("                / - -\n      / - - - -      \n- - -                \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_steep_line_steps_diagonally() {
+        let canvas = plot(Box::new(Line::new(Coords(0, 4), Coords(1, 0)).unwrap())).unwrap();
+
+        assert_eq!("|  \n|  \n|  \n  \\\n  |\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_two_to_one_line_steps_diagonally() {
+        let canvas = plot(Box::new(Line::new(Coords(0, 0), Coords(10, 5)).unwrap())).unwrap();
+
+        // horizontal runs of two cells, joined by diagonal steps
+        assert_eq!("                    /\n                / -  \n            / -      \n        / -          \n    / -              \n- -                  \n", render(&canvas, &Charset::ascii()));
+    }
+}
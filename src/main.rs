@@ -1,591 +1,495 @@
-//use std::collections::HashSet;
-// @TODO wanna use HashSet as coords can only be unique, see
-// http://stackoverflow.com/questions/27828487/hashmap-with-hashset-as-key
-// (need to implement Hash for Hashmap ...)
-
+extern crate ascii_art;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process;
+
+use ascii_art::{combine, parse_scene_with, plot_in, render_ansi_with_to, render_braille_to, render_half_blocks_to, render_quadrants_to, render_with_to, supports_color, Arc, Charset, Circle, CombinedObject, CoordinateSystem, Coords, CubicBezier, Dimensions, Ellipse, Line, Plottable, Polygon, Polyline, QuadraticBezier, Rectangle, RenderOptions, Representation, Scene, TERMINAL_CELL_ASPECT_RATIO};
+
+const USAGE: &str = "usage: ascii-art [options] [command ...]
+
+Draws the given shapes. If neither a command nor a scene file is given,
+commands are read from stdin.
+
+commands:
+    line X0,Y0 X1,Y1       line from (X0, Y0) to (X1, Y1)
+    circle X,Y RADIUS      circle around (X, Y)
+    ellipse X,Y RX RY      ellipse around (X, Y) with the horizontal and vertical radius
+    arc X,Y RADIUS A0 A1   arc around (X, Y) from angle A0 counter-clockwise to A1 in degrees
+    rectangle X0,Y0 X1,Y1  rectangle with the opposite corners (X0, Y0) and (X1, Y1)
+    polyline X,Y X,Y ...   lines connecting the vertices
+    polygon X,Y X,Y ...    lines connecting the vertices, the last one back to the first one
+    bezier X,Y X,Y X,Y     quadratic bezier curve from the first to the last point,
+    bezier X,Y X,Y X,Y X,Y cubic bezier curve, the points in between are the control points
+    filled COMMAND ...     fills a circle, ellipse, rectangle or polygon
+
+options:
+    -w, --width N          width of the output, defaults to the size of the drawing
+    -h, --height N         height of the output, defaults to the size of the drawing
+    -c, --charset CHARSET  'ascii', 'unicode', 'light', 'heavy', 'double', 'rounded' or six glyphs
+                           for canvas, vertical, horizontal, diagonal left to right, diagonal
+                           right to left and circle
+    -f, --fill GLYPH       glyph for the inside of filled shapes
+    -r, --renderer MODE    'text' draws a glyph for every cell. 'braille', 'half-blocks' and
+                           'quadrants' draw 2 by 4, 1 by 2 and 2 by 2 cells as one character and
+                           ignore the charset
+        --separator STRING written between the glyphs of the text renderer, defaults to a space
+        --stretch N        every glyph of the text renderer is N characters wide
+        --trim             remove the whitespace at the end of every line
+        --round            stretch circles to look round with the renderer, separator and stretch
+    -y, --y-axis DIRECTION 'up' puts y = 0 at the bottom, 'down' at the top of the output,
+                           overrides the y_axis of a scene
+    -s, --scene FILE       draw the shapes declared in the scene file FILE
+    -o, --output FILE      write to FILE instead of stdout
+        --help             print this message, note that -h is short for --height";
 
 // -- structs --
-#[derive(Debug, PartialEq, Clone)]
-enum Representation {
-    Canvas,
-    VerticalLine,
-    HorizontalLine,
-    DiagonalLineLeftToRight,
-    DiagonalLineRightToLeft,
-    Circle,
+#[derive(Debug, PartialEq)]
+enum Renderer {
+    Text,
+    Braille,
+    HalfBlocks,
+    Quadrants,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    width: Option<u32>,
+    height: Option<u32>,
+    charset: Charset,
+    renderer: Renderer,
+    render_options: RenderOptions,
+    round: bool,
+    // a scene can choose the y axis, unless it is given on the command line
+    coordinate_system: Option<CoordinateSystem>,
+    scene: Option<String>,
+    output: Option<String>,
+    help: bool,
+    commands: Vec<String>,
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Coords(u32, u32);
-
-impl Coords {
-    fn from_dimensions(dimensions: Dimensions) -> Vec<Coords> {
-        fn coords_from_index(i: u32, dimensions: Dimensions) -> Coords {
-            if i < dimensions.0 { Coords(i, 0) }
-            else { Coords(i % dimensions.0, i / dimensions.0) }
-        };
-
-        (0..(dimensions.0 * dimensions.1))
-            .map(|i| { coords_from_index(i, dimensions) })
-            .collect()
-    }
-}
-
-#[derive(Debug, PartialEq, Clone)]
-struct PlottedCoords(u32, u32, Representation);
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Dimensions(u32, u32);
-
-impl Dimensions {
-    fn from_coords(coords: &Vec<Coords>) -> Dimensions {
-        let x = Dimensions::get_max_coord_from_coords(coords.clone().iter(), &|a| a.0);
-        let y = Dimensions::get_max_coord_from_coords(coords.clone().iter(), &|a| a.1);
-
-        Dimensions(x + 1, y + 1)
+// -- functions --
+fn parse_options(args: Vec<String>) -> Result<Options, String> {
+    let mut options = Options {
+        width: None,
+        height: None,
+        charset: Charset::ascii(),
+        renderer: Renderer::Text,
+        render_options: RenderOptions::new(),
+        round: false,
+        coordinate_system: None,
+        scene: None,
+        output: None,
+        help: false,
+        commands: vec![],
+    };
+
+    let mut fill = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-w" | "--width" => options.width = Some(parse_number(&next_value(&arg, &mut args)?)?),
+            "-h" | "--height" => options.height = Some(parse_number(&next_value(&arg, &mut args)?)?),
+            "-c" | "--charset" => options.charset = parse_charset(&next_value(&arg, &mut args)?)?,
+            "-f" | "--fill" => fill = Some(parse_glyph(&next_value(&arg, &mut args)?)?),
+            "-r" | "--renderer" => options.renderer = parse_renderer(&next_value(&arg, &mut args)?)?,
+            "--separator" => options.render_options = options.render_options.separator(&next_value(&arg, &mut args)?),
+            "--stretch" => options.render_options = options.render_options.stretch(parse_number(&next_value(&arg, &mut args)?)?),
+            "--trim" => options.render_options = options.render_options.trim(),
+            "--round" => options.round = true,
+            "-y" | "--y-axis" => options.coordinate_system = Some(parse_y_axis(&next_value(&arg, &mut args)?)?),
+            "-s" | "--scene" => options.scene = Some(next_value(&arg, &mut args)?),
+            "-o" | "--output" => options.output = Some(next_value(&arg, &mut args)?),
+            "--help" => options.help = true,
+            _ => options.commands.push(arg),
+        }
     }
 
-    // @TODO private
-    fn get_max_coord_from_coords<'a, I: Iterator<Item=&'a Coords>>(coords: I, pluck_fn: &Fn(&Coords) -> u32) -> u32 {
-        coords.map(pluck_fn).max().unwrap()
+    // the fill glyph applies to any charset
+    if let Some(glyph) = fill {
+        options.charset = options.charset.with_glyph(Representation::Fill, glyph);
     }
-}
-
 
-// -- Plottable objects --
-trait Plottable {
-    // @TODO it's not really clear that get_dimensions() returns the canvas size needed for the object, not the *actual* dimensions for the object
-// a horizontal line at (1, 1) to (3, 1) has the canvas size (4, 2) and dimension is actually (3, 1). get_dimensions at the moment will return (4, 2)
-    fn get_dimensions(&self) -> Dimensions;
-
-    fn get_coords(&self) -> Vec<Coords>;
-
-    fn get_representation_at(&self, coords: Coords) -> Option<Representation>;
+    Ok(options)
 }
 
-struct CombinedObject(Dimensions, Vec<Box<Plottable>>);
-
-impl Plottable for CombinedObject {
-    fn get_dimensions(&self) -> Dimensions {
-        self.0
-    }
-
-    fn get_coords(&self) -> Vec<Coords> {
-        let mut coords = vec![];
-
-        for contained_plottable in self.1.iter() {
-            let contained_coords = contained_plottable.get_coords();
-            coords.extend(contained_coords.clone());
-        }
-
-        coords
-    }
-
-    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
-        for contained_plottable in self.1.iter() {
-            let contained_coords = contained_plottable.get_coords();
-            let result = contained_coords.iter().find(|a| **a == coords);
-
-            match result {
-                Some(matched_contained_coords) => { return contained_plottable.get_representation_at(*matched_contained_coords) },
-                None => {},
-            };
-        }
-
-        None
+// the aspect ratio (height / width) of a cell in the output, the block renderers draw several cells as one
+// character
+fn get_aspect_ratio(options: &Options) -> f64 {
+    match options.renderer {
+        Renderer::Text => options.render_options.get_aspect_ratio(),
+        Renderer::Braille => TERMINAL_CELL_ASPECT_RATIO * 2.0 / 4.0,
+        Renderer::HalfBlocks => TERMINAL_CELL_ASPECT_RATIO / 2.0,
+        Renderer::Quadrants => TERMINAL_CELL_ASPECT_RATIO * 2.0 / 2.0,
     }
 }
 
-
-#[derive(Debug)]
-struct Line(Dimensions, Vec<Coords>);
-
-impl Line {
-    // @TODO make private
-    fn get_line_representation(start: Coords, end: Coords) -> Representation {
-        let Coords(x0, y0) = start;
-        let Coords(x1, y1) = end;
-
-        if y0 == y1 {
-            Representation::HorizontalLine
-        } else if x0 == x1 {
-            Representation::VerticalLine
-        } else if y0 > y1 {
-            Representation::DiagonalLineRightToLeft
-        } else {
-            Representation::DiagonalLineLeftToRight
-        }
-    }
-
-    fn rotate(line: Line, rotation_center: Coords) -> Line {
-        // @TODO this is copy/pasted from get_representation
-        // @TODO only implemented for vertical to horizontal, left
-        // @TODO cut off if mapped x/y gets negative
-        let mut coords = line.get_coords();
-        coords.sort_by_key(|&Coords(x, y)|(x, y));
-
-        let start = coords.first().unwrap();
-        let end = coords.last().unwrap();
-
-        // @FIXME currently we can only rotate from start or end of the line
-        assert!(
-            (start.0 == rotation_center.0 && start.1 == rotation_center.1) ||
-            (end.0 == rotation_center.0 && end.1 == rotation_center.1)
-        );
-
-        // @TODO assert that line representation is either horizontal or vertical for now
-        let line_representation = Line::get_line_representation(*start, *end);
-        // 1 = down
-        // 2 = up
-        let rotation_direction = if rotation_center.0 == start.0 && rotation_center.1 == start.1 { 1 } else { 2 };
-
-
-        let mut coords_mapped = vec![];
-
-        // @TODO use rotation matrix?
-        for (i, &Coords(x, y)) in coords.iter().enumerate() {
-            if line_representation == Representation::HorizontalLine && rotation_direction == 1 {
-                let mapped_x = x - i as u32;
-                let mapped_y = y - i as u32;
-
-                coords_mapped.push(Coords(mapped_x, mapped_y));
-            } else if line_representation == Representation::VerticalLine && rotation_direction == 1 {
-                let mapped_x = x + i as u32;
-                let mapped_y = y - i as u32;
-
-                coords_mapped.push(Coords(mapped_x, mapped_y));
-            } else if line_representation == Representation::HorizontalLine && rotation_direction == 2 {
-                let mapped_x = x + i as u32;
-                let mapped_y = y + i as u32;
-
-                coords_mapped.push(Coords(mapped_x, mapped_y));
-            } else if line_representation == Representation::VerticalLine && rotation_direction == 2 {
-                let mapped_x = x - i as u32;
-                let mapped_y = y + i as u32;
-
-                coords_mapped.push(Coords(mapped_x, mapped_y));
-            }
-        }
-
-        let dimensions = Dimensions::from_coords(&coords);
-        Line(dimensions, coords_mapped)
-    }
-
-    fn new(start: Coords, end: Coords) -> Line {
-        // how to make this nicer and use tuple deconstruction?
-        let x0 = start.0 as i32;
-        let y0 = start.1 as i32;
-        let x1 = end.0 as i32;
-        let y1 = end.1 as i32;
-
-        let dx = ((x1 - x0)).abs();
-
-        let sx: i32 = if x0 < x1 { 1 } else { -1 };
-
-        let dy = ((y1 - y0)).abs();
-        let sy: i32 = if y0 < y1 { 1 } else { -1 };
-
-        let tmp = if dx > dy { dx } else { -dy };
-        let mut err = tmp / 2;
-        let mut e2;
-
-        let mut x0_m = x0;
-        let mut y0_m = y0;
-
-        let mut coords = vec![];
-
-        loop {
-            coords.push(Coords(x0_m as u32, y0_m as u32));
-
-            if x0_m == x1 as i32 && y0_m == y1 as i32 {
-                break;
-            }
-
-            e2 = err;
-
-            if e2 > -dx {
-                err -= dy;
-                x0_m += sx;
-            }
-
-            if e2 < dy {
-                err += dx;
-                y0_m += sy;
-            }
-        }
-
-        let dimensions = Dimensions::from_coords(&coords);
-        Line(dimensions, coords)
-    }
+fn next_value<I: Iterator<Item=String>>(option: &str, args: &mut I) -> Result<String, String> {
+    args.next().ok_or_else(|| format!("missing value for {}", option))
 }
 
-impl Plottable for Line {
-    fn get_dimensions(&self) -> Dimensions {
-        self.0
-    }
-
-    fn get_coords(&self) -> Vec<Coords> {
-        self.1.clone()
-    }
-
-    fn get_representation_at(&self, _: Coords) -> Option<Representation> {
-        let mut coords = self.get_coords();
-        coords.sort_by_key(|&Coords(x, y)|(x, y));
-
-        let start = coords.first().unwrap();
-        let end = coords.last().unwrap();
-
-        let representation = Line::get_line_representation(*start, *end);
-        Some(representation)
-    }
+fn parse_number(value: &str) -> Result<u32, String> {
+    value.parse::<u32>().map_err(|_| format!("invalid number '{}'", value))
 }
 
-#[derive(Debug)]
-struct Circle(Dimensions, Vec<Coords>);
-
-impl Circle {
-    fn new(point: Coords, radius: u32) -> Circle {
-        let Coords(x0, y0) = point;
-
-        let mut x = radius;
-        let mut y = 0;
-
-        let mut err: i32 = 0;
-
-        let mut coords = vec![];
-
-        while x >= y {
-            coords.push(Coords(x0 + x, y0 + y));
-            coords.push(Coords(x0 + y, y0 + x));
-            coords.push(Coords(x0 - y, y0 + x));
-            coords.push(Coords(x0 - x, y0 + y));
-            coords.push(Coords(x0 - x, y0 - y));
-            coords.push(Coords(x0 - y, y0 - x));
-            coords.push(Coords(x0 + y, y0 - x));
-            coords.push(Coords(x0 + x, y0 - y));
-
-            y += 1;
-            err += 1 + 2 * y as i32;
-
-            if 2 * (err - x as i32) + 1 > 0 {
-                x -= 1;
-                err += 1 - 2 * x as i32;
-            }
-        }
-
-        let dimensions = Dimensions::from_coords(&coords);
-        Circle(dimensions, coords)
+fn parse_charset(value: &str) -> Result<Charset, String> {
+    if let Some(charset) = Charset::from_name(value) {
+        return Ok(charset);
     }
-}
 
-impl Plottable for Circle {
-    fn get_dimensions(&self) -> Dimensions {
-        self.0
-    }
+    let glyphs = value.chars().collect::<Vec<_>>();
 
-    fn get_coords(&self) -> Vec<Coords> {
-        self.1.clone()
+    if glyphs.len() != 6 {
+        return Err(format!("invalid charset '{}', expected 'ascii', 'unicode', 'light', 'heavy', 'double', 'rounded' or six glyphs", value));
     }
 
-    fn get_representation_at(&self, _: Coords) -> Option<Representation> {
-        Some(Representation::Circle)
+    let representations = vec![
+        Representation::Canvas,
+        Representation::VerticalLine,
+        Representation::HorizontalLine,
+        Representation::DiagonalLineLeftToRight,
+        Representation::DiagonalLineRightToLeft,
+        Representation::Circle,
+    ];
+
+    Ok(representations.into_iter().zip(glyphs).fold(Charset::ascii(), |charset, (representation, glyph)| charset.with_glyph(representation, glyph)))
+}
+
+fn parse_renderer(value: &str) -> Result<Renderer, String> {
+    match value {
+        "text" => Ok(Renderer::Text),
+        "braille" => Ok(Renderer::Braille),
+        "half-blocks" => Ok(Renderer::HalfBlocks),
+        "quadrants" => Ok(Renderer::Quadrants),
+        _ => Err(format!("invalid renderer '{}', expected 'text', 'braille', 'half-blocks' or 'quadrants'", value)),
     }
 }
 
-#[derive(Debug)]
-struct Canvas(Dimensions, Vec<PlottedCoords>);
-
-// -- functions --
-fn combine<T: Plottable + 'static, U: Plottable + 'static>(a: Box<T>, b: Box<U>) -> CombinedObject {
-    let mut contained_objects: Vec<Box<Plottable>> = vec![];
-    contained_objects.push(a);
-    contained_objects.push(b);
-
-    let mut contained_coords = vec![];
-    contained_coords.extend(contained_objects[0].get_coords().iter());
-    contained_coords.extend(contained_objects[1].get_coords().iter());
-
-    let dimensions = Dimensions::from_coords(&contained_coords);
-    CombinedObject(dimensions, contained_objects)
-}
-
-fn plot(a: Box<Plottable>) -> Canvas {
-    let mut canvas_coords = Coords::from_dimensions(a.get_dimensions())
-        .iter()
-        .map(|fill_coords| {
-            match a.get_representation_at(*fill_coords) {
-                Some(representation) => PlottedCoords(fill_coords.0, fill_coords.1, representation),
-                None => PlottedCoords(fill_coords.0, fill_coords.1, Representation::Canvas),
-            }
-        }).collect::<Vec<_>>();
-
-    canvas_coords.sort_by_key(|&PlottedCoords(x, y, _)| (!y, x));
-
-    Canvas(a.get_dimensions(), canvas_coords)
-}
-
-fn draw(canvas: Canvas) {
-    for PlottedCoords(x, _, representation) in canvas.1 {
-        let chr = match representation {
-            Representation::Circle => 'o',
-            Representation::Canvas => ' ',
-            Representation::HorizontalLine => '-',
-            Representation::VerticalLine => '|',
-            Representation::DiagonalLineLeftToRight => '/',
-            Representation::DiagonalLineRightToLeft => '\\',
-            //_ => '?',
-        };
-
-        if x == (canvas.0).0 - 1 { println!("{}", chr); }
-        else { print!("{} ", chr); }
+fn parse_y_axis(value: &str) -> Result<CoordinateSystem, String> {
+    match value {
+        "up" => Ok(CoordinateSystem::Cartesian),
+        "down" => Ok(CoordinateSystem::Screen),
+        _ => Err(format!("invalid y axis '{}', expected 'up' or 'down'", value)),
     }
 }
 
-// -- tests --
-fn fixtures_diagonal_line_ltr() -> Line {
-    Line::new(Coords(0, 0), Coords(2, 2))
-}
-
-fn fixtures_horizontal_line() -> Line {
-    Line::new(Coords(0, 0), Coords(2, 0))
+fn parse_angle(value: &str) -> Result<f64, String> {
+    value.parse::<f64>().ok().filter(|angle| angle.is_finite()).ok_or_else(|| format!("invalid angle '{}'", value))
 }
 
-fn fixtures_vertical_line() -> Line {
-    Line::new(Coords(0, 0), Coords(0, 3))
-}
+fn parse_glyph(value: &str) -> Result<char, String> {
+    let mut chars = value.chars();
 
-fn fixtures_circle() -> Circle {
-    Circle::new(Coords(1, 1), 1)
+    match (chars.next(), chars.next()) {
+        (Some(glyph), None) => Ok(glyph),
+        _ => Err(format!("invalid glyph '{}', expected a single character", value)),
+    }
 }
 
-#[test]
-fn test_new_circle() {
-    let circle = Circle::new(Coords(1, 1), 1);
+fn parse_coords(value: &str) -> Result<Coords, String> {
+    let parts = value.split(',').collect::<Vec<_>>();
 
-    assert_eq!(Dimensions(3, 3), circle.get_dimensions());
-    // @TODO this is not so nice, some coords are duplicated
-    assert_eq!(vec![Coords(2, 1), Coords(1, 2), Coords(1, 2), Coords(0, 1), Coords(0, 1), Coords(1, 0), Coords(1, 0), Coords(2, 1)], circle.get_coords());
-}
+    if parts.len() != 2 {
+        return Err(format!("invalid coordinates '{}', expected X,Y", value));
+    }
 
-#[test]
-fn test_rotate_line_vertical_to_horizontal()
-{
-    let line = Line::new(Coords(5, 5), Coords(5, 9));
-    let rotated_line = Line::rotate(line, Coords(5, 5));
+    let x = parts[0].trim().parse::<i32>().map_err(|_| format!("invalid coordinates '{}', expected X,Y", value))?;
+    let y = parts[1].trim().parse::<i32>().map_err(|_| format!("invalid coordinates '{}', expected X,Y", value))?;
 
-    assert_eq!(vec![Coords(5, 5), Coords(6, 5), Coords(7, 5), Coords(8, 5), Coords(9, 5)], rotated_line.get_coords());
+    Ok(Coords(x, y))
 }
 
-#[test]
-fn test_rotate_line_horizontal_to_vertical()
-{
-    let line = Line::new(Coords(5, 5), Coords(9, 5));
-    let rotated_line = Line::rotate(line, Coords(5, 5));
-
-    assert_eq!(vec![Coords(5, 5), Coords(5, 4), Coords(5, 3), Coords(5, 2), Coords(5, 1)], rotated_line.get_coords());
-}
+// circles are stretched by the aspect ratio if there is one
+fn parse_shapes(commands: &[String], aspect_ratio: Option<f64>) -> Result<Vec<Box<dyn Plottable>>, String> {
+    let mut shapes: Vec<Box<dyn Plottable>> = vec![];
+    let mut tokens = commands.iter().peekable();
 
-#[test]
-fn test_new_horizontal_line_1() {
-    let line = Line::new(Coords(0, 0), Coords(3, 0));
+    while let Some(command) = tokens.next() {
+        let filled = command == "filled";
 
-    assert_eq!(Dimensions(4, 1), line.get_dimensions());
-    assert_eq!(vec![Coords(0, 0), Coords(1, 0), Coords(2, 0), Coords(3, 0)], line.get_coords());
+        let command = if filled {
+            tokens.next().ok_or_else(|| "missing command after 'filled'".to_string())?
+        } else {
+            command
+        };
 
-    assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(0, 0)).unwrap());
-    assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(1, 0)).unwrap());
-    assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(2, 0)).unwrap());
-    assert_eq!(Representation::HorizontalLine, line.get_representation_at(Coords(3, 0)).unwrap());
-}
+        if filled && !["circle", "ellipse", "rectangle", "polygon"].contains(&command.as_str()) {
+            return Err(format!("'{}' can't be filled, only circle, ellipse, rectangle and polygon", command));
+        }
 
-#[test]
-fn test_new_horizontal_line_2() {
-    let line = Line::new(Coords(1, 1), Coords(3, 1));
+        let mut argument = || tokens.next().ok_or_else(|| format!("missing argument for '{}'", command));
+
+        match command.as_str() {
+            "line" => {
+                let start = parse_coords(argument()?)?;
+                let end = parse_coords(argument()?)?;
+
+                shapes.push(Box::new(Line::new(start, end).map_err(|e| e.to_string())?));
+            },
+            "circle" => {
+                let point = parse_coords(argument()?)?;
+                let radius = parse_number(argument()?)?;
+
+                let circle = match aspect_ratio {
+                    Some(aspect_ratio) => Circle::with_aspect_ratio(point, radius, aspect_ratio),
+                    None => Circle::new(point, radius),
+                };
+
+                let circle = circle.map_err(|e| e.to_string())?;
+                shapes.push(Box::new(if filled { circle.filled() } else { circle }));
+            },
+            "ellipse" => {
+                let point = parse_coords(argument()?)?;
+                let radius_x = parse_number(argument()?)?;
+                let radius_y = parse_number(argument()?)?;
+
+                let ellipse = Ellipse::new(point, radius_x, radius_y).map_err(|e| e.to_string())?;
+                shapes.push(Box::new(if filled { ellipse.filled() } else { ellipse }));
+            },
+            "arc" => {
+                let point = parse_coords(argument()?)?;
+                let radius = parse_number(argument()?)?;
+                let start_angle = parse_angle(argument()?)?;
+                let end_angle = parse_angle(argument()?)?;
+
+                shapes.push(Box::new(Arc::new(point, radius, start_angle, end_angle).map_err(|e| e.to_string())?));
+            },
+            "rectangle" => {
+                let corner = parse_coords(argument()?)?;
+                let opposite_corner = parse_coords(argument()?)?;
+
+                let rectangle = Rectangle::new(corner, opposite_corner).map_err(|e| e.to_string())?;
+                shapes.push(Box::new(if filled { rectangle.filled() } else { rectangle }));
+            },
+            "polyline" | "polygon" => {
+                // the vertices end at the next command
+                let mut vertices = vec![];
+
+                while let Some(vertex) = tokens.next_if(|token| token.contains(',')) {
+                    vertices.push(parse_coords(vertex)?);
+                }
+
+                if command == "polyline" {
+                    shapes.push(Box::new(Polyline::new(vertices).map_err(|e| e.to_string())?));
+                } else {
+                    let polygon = Polygon::new(vertices).map_err(|e| e.to_string())?;
+                    shapes.push(Box::new(if filled { polygon.filled() } else { polygon }));
+                }
+            },
+            "bezier" => {
+                let mut points = vec![];
+
+                while let Some(point) = tokens.next_if(|token| token.contains(',')) {
+                    points.push(parse_coords(point)?);
+                }
+
+                match points.len() {
+                    3 => shapes.push(Box::new(QuadraticBezier::new(points[0], points[1], points[2]).map_err(|e| e.to_string())?)),
+                    4 => shapes.push(Box::new(CubicBezier::new(points[0], points[1], points[2], points[3]).map_err(|e| e.to_string())?)),
+                    _ => return Err(format!("a bezier curve needs 3 or 4 points, got {}", points.len())),
+                }
+            },
+            _ => return Err(format!("unknown command '{}'", command)),
+        }
+    }
 
-    assert_eq!(Dimensions(4, 2), line.get_dimensions());
-    assert_eq!(vec![Coords(1, 1), Coords(2, 1), Coords(3, 1)], line.get_coords());
+    Ok(shapes)
 }
 
-#[test]
-fn test_new_vertical_line() {
-    let line = Line::new(Coords(0, 0), Coords(0, 3));
+fn combine_shapes(shapes: Vec<Box<dyn Plottable>>) -> Result<Box<dyn Plottable>, String> {
+    let mut shapes = shapes.into_iter();
+    let first = shapes.next().ok_or_else(|| "nothing to draw".to_string())?;
 
-    assert_eq!(Dimensions(1, 4), line.get_dimensions());
-    assert_eq!(vec![Coords(0, 0), Coords(0, 1), Coords(0, 2), Coords(0, 3)], line.get_coords());
-
-    assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 0)).unwrap());
-    assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 1)).unwrap());
-    assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 2)).unwrap());
-    assert_eq!(Representation::VerticalLine, line.get_representation_at(Coords(0, 3)).unwrap());
+    shapes.try_fold(first, |combined, shape| -> Result<Box<dyn Plottable>, String> {
+        Ok(Box::new(combine(Box::new(combined), Box::new(shape)).map_err(|e| e.to_string())?))
+    })
 }
 
-#[test]
-fn test_new_diagonal_line_left_to_right() {
-    let line = Line::new(Coords(0, 0), Coords(3, 3));
-
-    assert_eq!(Dimensions(4, 4), line.get_dimensions());
-    assert_eq!(vec![Coords(0, 0), Coords(1, 1), Coords(2, 2),  Coords(3, 3)], line.get_coords());
+fn resize(drawing: Box<dyn Plottable>, width: Option<u32>, height: Option<u32>) -> CombinedObject {
+    let Dimensions(fit_width, fit_height) = drawing.get_dimensions();
+    let dimensions = Dimensions(width.unwrap_or(fit_width), height.unwrap_or(fit_height));
 
-    assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(0, 0)).unwrap());
-    assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(1, 1)).unwrap());
-    assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(2, 2)).unwrap());
-    assert_eq!(Representation::DiagonalLineLeftToRight, line.get_representation_at(Coords(3, 3)).unwrap());
+    CombinedObject(dimensions, vec![drawing])
 }
 
-#[test]
-fn test_new_diagonal_line_right_to_left() {
-    let line = Line::new(Coords(0, 3), Coords(3, 0));
+fn read_scene(path: &str, aspect_ratio: f64) -> Result<Scene, String> {
+    let mut source = String::new();
 
-    assert_eq!(Dimensions(4, 4), line.get_dimensions());
-    assert_eq!(vec![Coords(0, 3), Coords(1, 2), Coords(2, 1),  Coords(3, 0)], line.get_coords());
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut source))
+        .map_err(|e| format!("could not read {}: {}", path, e))?;
 
-    assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(0, 3)).unwrap());
-    assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(1, 2)).unwrap());
-    assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(2, 1)).unwrap());
-    assert_eq!(Representation::DiagonalLineRightToLeft, line.get_representation_at(Coords(3, 0)).unwrap());
+    parse_scene_with(&source, aspect_ratio).map_err(|e| format!("{}: {}", path, e))
 }
 
-#[test]
-fn test_get_dimension_from_coords() {
-    let coords = vec![Coords(0, 0), Coords(2, 1), Coords(5, 9), Coords(10, 5)];
-    let dimensions = Dimensions::from_coords(&coords);
-
-    assert_eq!(11, dimensions.0);
-    assert_eq!(10, dimensions.1);
-}
-
-#[test]
-fn test_combine_expands_dimensions_to_fit_largest_object_line() {
-    let line_1 = fixtures_diagonal_line_ltr();
-    let line_2 = fixtures_horizontal_line();
-
-    // combined object is supposed to be large enough to contain 2 lines
-    let lines_combined = combine(Box::new(line_1), Box::new(line_2));
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut options = parse_options(args)?;
 
-    assert_eq!((lines_combined.0).0, 3);
-    assert_eq!((lines_combined.0).1, 3);
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
 
-    assert_eq!(lines_combined.1.len(), 2);
-}
+    if options.commands.is_empty() && options.scene.is_none() {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map_err(|e| format!("could not read stdin: {}", e))?;
+        options.commands = input.split_whitespace().map(|s| s.to_string()).collect();
+    }
 
-#[test]
-fn test_combine_expands_dimensions_to_fit_largest_object_circle() {
-    let line_1 = fixtures_diagonal_line_ltr();
-    let circle_1 = fixtures_circle();
+    let aspect_ratio = get_aspect_ratio(&options);
+    let mut shapes = parse_shapes(&options.commands, if options.round { Some(aspect_ratio) } else { None })?;
 
-    // combined object is supposed to be large enough to contain 2 lines
-    let lines_combined = combine(Box::new(line_1), Box::new(circle_1));
+    let mut coordinate_system = options.coordinate_system;
 
-    assert_eq!((lines_combined.0).0, 3);
-    assert_eq!((lines_combined.0).1, 3);
+    if let Some(ref path) = options.scene {
+        let scene = read_scene(path, aspect_ratio)?;
+        coordinate_system = coordinate_system.or(Some(scene.get_coordinate_system()));
+        shapes.insert(0, Box::new(scene));
+    }
 
-    assert_eq!(lines_combined.1.len(), 2);
+    let drawing = resize(combine_shapes(shapes)?, options.width, options.height);
+    let canvas = plot_in(Box::new(drawing), coordinate_system.unwrap_or_default()).map_err(|e| e.to_string())?;
+
+    // shapes styled in a scene are only drawn in color on a terminal
+    let colored = options.output.is_none() && supports_color(&io::stdout());
+
+    let write = |mut writer: &mut dyn Write| match options.renderer {
+        Renderer::Text if colored => render_ansi_with_to(&canvas, &options.charset, &options.render_options, &mut writer),
+        Renderer::Text => render_with_to(&canvas, &options.charset, &options.render_options, &mut writer),
+        Renderer::Braille => render_braille_to(&canvas, &mut writer),
+        Renderer::HalfBlocks => render_half_blocks_to(&canvas, &mut writer),
+        Renderer::Quadrants => render_quadrants_to(&canvas, &mut writer),
+    };
+
+    match options.output {
+        Some(ref path) => File::create(path)
+            .and_then(|mut file| write(&mut file))
+            .map_err(|e| format!("could not write {}: {}", path, e)),
+        None => write(&mut io::stdout().lock())
+            .map_err(|e| format!("could not write to stdout: {}", e)),
+    }
 }
 
-#[test]
-fn test_plot_merged_object() {
-    let line_1 = fixtures_diagonal_line_ltr();
-    let line_2 = fixtures_horizontal_line();
-    let line_3 = fixtures_vertical_line();
-
-    // combined object is supposed to be large enough to contain 2 lines
-    let lines_combined_1 = combine(Box::new(line_1), Box::new(line_2));
-    let lines_combined_2 = combine(Box::new(lines_combined_1), Box::new(line_3));
-
-    let canvas = plot(Box::new(lines_combined_2));
-
-    assert_eq!((canvas.0).0, 3);
-    assert_eq!((canvas.0).1, 4);
-
-    assert_eq!((canvas.1).len(), 12);
-
-    assert_eq!(canvas.1[0], PlottedCoords(0, 3, Representation::VerticalLine));
-    assert_eq!(canvas.1[1], PlottedCoords(1, 3, Representation::Canvas));
-    assert_eq!(canvas.1[2], PlottedCoords(2, 3, Representation::Canvas));
-
-    assert_eq!(canvas.1[3], PlottedCoords(0, 2, Representation::VerticalLine));
-    assert_eq!(canvas.1[4], PlottedCoords(1, 2, Representation::Canvas));
-    assert_eq!(canvas.1[5], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight));
-
-    assert_eq!(canvas.1[6], PlottedCoords(0, 1, Representation::VerticalLine));
-    assert_eq!(canvas.1[7], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[8], PlottedCoords(2, 1, Representation::Canvas));
+fn main () {
+    let args = env::args().skip(1).collect::<Vec<_>>();
 
-    assert_eq!(canvas.1[9], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[10], PlottedCoords(1, 0, Representation::HorizontalLine));
-    assert_eq!(canvas.1[11], PlottedCoords(2, 0, Representation::HorizontalLine));
+    if let Err(message) = run(args) {
+        eprintln!("error: {}\n\n{}", message, USAGE);
+        process::exit(1);
+    }
 }
 
-#[test]
-fn test_plot_line() {
-    let line_1 = fixtures_diagonal_line_ltr();
-    let line_2 = fixtures_horizontal_line();
-
-    // combined object is supposed to be large enough to contain 2 lines
-    let lines_combined = combine(Box::new(line_1), Box::new(line_2));
-    let canvas = plot(Box::new(lines_combined));
-
-    assert_eq!((canvas.0).0, 3);
-    assert_eq!((canvas.0).1, 3);
-
-    assert_eq!((canvas.1).len(), 9);
-
-    assert_eq!(canvas.1[0], PlottedCoords(0, 2, Representation::Canvas));
-    assert_eq!(canvas.1[1], PlottedCoords(1, 2, Representation::Canvas));
-    assert_eq!(canvas.1[2], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight));
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ascii_art::{plot, render};
 
-    assert_eq!(canvas.1[3], PlottedCoords(0, 1, Representation::Canvas));
-    assert_eq!(canvas.1[4], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[5], PlottedCoords(2, 1, Representation::Canvas));
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
 
-    assert_eq!(canvas.1[6], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[7], PlottedCoords(1, 0, Representation::HorizontalLine));
-    assert_eq!(canvas.1[8], PlottedCoords(2, 0, Representation::HorizontalLine));
-}
+    #[test]
+    fn test_parse_options() {
+        let options = parse_options(args("-w 10 line 0,0 2,2 -f = --charset .|-/\\* -o out.txt")).unwrap();
+
+        assert_eq!(Some(10), options.width);
+        assert_eq!(None, options.height);
+        assert_eq!('.', options.charset.get_glyph(&Representation::Canvas));
+        assert_eq!('*', options.charset.get_glyph(&Representation::Circle));
+        assert_eq!('=', options.charset.get_glyph(&Representation::Fill));
+        assert_eq!(Some("out.txt".to_string()), options.output);
+        assert_eq!(args("line 0,0 2,2"), options.commands);
+    }
 
-#[test]
-fn test_plot_circle() {
-    let line_1 = fixtures_diagonal_line_ltr();
-    let circle_1 = fixtures_circle();
+    #[test]
+    fn test_parse_render_options() {
+        assert_eq!(RenderOptions::new(), parse_options(args("line 0,0 2,2")).unwrap().render_options);
+        assert_eq!(RenderOptions::new().separator("|").stretch(2).trim(), parse_options(args("--separator | --stretch 2 --trim")).unwrap().render_options);
+        assert!(parse_options(args("--stretch wide")).is_err());
+    }
 
-    // combined object is supposed to be large enough to contain 2 lines
-    let lines_combined = combine(Box::new(line_1), Box::new(circle_1));
-    let canvas = plot(Box::new(lines_combined));
+    #[test]
+    fn test_parse_help() {
+        assert!(parse_options(args("line 0,0 2,2 --help")).unwrap().help);
+        assert!(!parse_options(args("--separator --help line 0,0 2,2")).unwrap().help);
+        assert!(!parse_options(args("-o --help line 0,0 2,2")).unwrap().help);
+        assert_eq!(Some(5), parse_options(args("-h 5")).unwrap().height);
+    }
 
-    assert_eq!((canvas.0).0, 3);
-    assert_eq!((canvas.0).1, 3);
+    #[test]
+    fn test_parse_y_axis() {
+        assert_eq!(None, parse_options(args("line 0,0 2,2")).unwrap().coordinate_system);
+        assert_eq!(Some(CoordinateSystem::Screen), parse_options(args("-y down")).unwrap().coordinate_system);
+        assert_eq!(Err("invalid y axis 'left', expected 'up' or 'down'".to_string()), parse_options(args("--y-axis left")).map(|_| ()));
+    }
 
-    assert_eq!((canvas.1).len(), 9);
+    #[test]
+    fn test_parse_options_rejects_invalid_charset() {
+        assert!(parse_options(args("--charset abc")).is_err());
+        assert_eq!(Charset::double(), parse_options(args("--charset double")).unwrap().charset);
+        assert_eq!(Renderer::Braille, parse_options(args("-r braille")).unwrap().renderer);
+        assert_eq!(Renderer::Quadrants, parse_options(args("--renderer quadrants")).unwrap().renderer);
+        assert_eq!(Err("invalid renderer 'dots', expected 'text', 'braille', 'half-blocks' or 'quadrants'".to_string()), parse_options(args("-r dots")).map(|_| ()));
+        assert!(parse_options(args("--width")).is_err());
+        assert!(parse_options(args("--fill ##")).is_err());
+    }
 
-    assert_eq!(canvas.1[0], PlottedCoords(0, 2, Representation::Canvas));
-    assert_eq!(canvas.1[1], PlottedCoords(1, 2, Representation::Circle));
-    assert_eq!(canvas.1[2], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight));
+    #[test]
+    fn test_round_circles_use_aspect_ratio_of_renderer() {
+        let aspect_ratio = |arguments: Vec<String>| get_aspect_ratio(&parse_options(arguments).unwrap());
 
-    assert_eq!(canvas.1[3], PlottedCoords(0, 1, Representation::Circle));
-    assert_eq!(canvas.1[4], PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[5], PlottedCoords(2, 1, Representation::Circle));
+        assert_eq!(1.0, aspect_ratio(args("--round")));
+        assert_eq!(2.0, aspect_ratio(vec!["--separator".to_string(), "".to_string()]));
+        assert_eq!(2.0, aspect_ratio(args("-r quadrants")));
+        assert!(parse_options(args("--round circle 0,0 2")).unwrap().round);
 
-    assert_eq!(canvas.1[6], PlottedCoords(0, 0, Representation::DiagonalLineLeftToRight));
-    assert_eq!(canvas.1[7], PlottedCoords(1, 0, Representation::Circle));
-    assert_eq!(canvas.1[8], PlottedCoords(2, 0, Representation::Canvas));
-}
+        let shapes = parse_shapes(&args("circle 0,0 2"), Some(2.0)).unwrap();
+        assert_eq!(Dimensions(9, 5), shapes[0].get_dimensions());
+    }
 
-#[test]
-fn test_plot_horizontal_line()
-{
-    let horizontal_line = fixtures_horizontal_line();
-    let canvas = plot(Box::new(horizontal_line));
+    #[test]
+    fn test_parse_shapes() {
+        let shapes = parse_shapes(&args("line 0,0 2,2 circle 3,3 1 rectangle 4,1 0,0 polygon 0,0 2,0 0,2 polyline 0,0 5,1 ellipse 3,2 3 2 arc 0,0 2 0 90 bezier 0,0 2,4 4,0"), None).unwrap();
+
+        assert_eq!(8, shapes.len());
+        assert_eq!(Dimensions(3, 3), shapes[0].get_dimensions());
+        assert_eq!(Dimensions(5, 5), shapes[1].get_dimensions());
+        assert_eq!(Dimensions(5, 2), shapes[2].get_dimensions());
+        assert_eq!(Dimensions(3, 3), shapes[3].get_dimensions());
+        assert_eq!(Dimensions(6, 2), shapes[4].get_dimensions());
+        assert_eq!(Dimensions(7, 5), shapes[5].get_dimensions());
+        assert_eq!(Dimensions(3, 3), shapes[6].get_dimensions());
+        assert_eq!(Dimensions(5, 3), shapes[7].get_dimensions());
+    }
 
-    assert_eq!((canvas.0).0, 3);
-    assert_eq!((canvas.0).1, 1);
+    #[test]
+    fn test_parse_shapes_reports_errors() {
+        assert_eq!(Err("unknown command 'square'".to_string()), parse_shapes(&args("square 1,1"), None).map(|_| ()));
+        assert_eq!(Err("missing argument for 'line'".to_string()), parse_shapes(&args("line 0,0"), None).map(|_| ()));
+        assert_eq!(Err("invalid coordinates '0;0', expected X,Y".to_string()), parse_shapes(&args("line 0;0 1,1"), None).map(|_| ()));
+        assert_eq!(Err("'line' can't be filled, only circle, ellipse, rectangle and polygon".to_string()), parse_shapes(&args("filled line 0,0 1,1"), None).map(|_| ()));
+        assert_eq!(Err("a bezier curve needs 3 or 4 points, got 2".to_string()), parse_shapes(&args("bezier 0,0 1,1"), None).map(|_| ()));
+        assert_eq!(Err("invalid angle 'up'".to_string()), parse_shapes(&args("arc 0,0 1 0 up"), None).map(|_| ()));
+        assert!(parse_shapes(&args("polygon 0,0 1,1 circle 1,1 1"), None).err().unwrap().starts_with("too few vertices"));
+    }
 
-    assert_eq!((canvas.1).len(), 3);
+    #[test]
+    fn test_render_filled_shape() {
+        let shapes = parse_shapes(&args("filled rectangle 0,0 3,2"), None).unwrap();
+        let canvas = plot(Box::new(combine_shapes(shapes).unwrap())).unwrap();
 
-    assert_eq!(canvas.1[0], PlottedCoords(0, 0, Representation::HorizontalLine));
-    assert_eq!(canvas.1[1], PlottedCoords(1, 0, Representation::HorizontalLine));
-    assert_eq!(canvas.1[2], PlottedCoords(2, 0, Representation::HorizontalLine));
-}
+        assert_eq!("+ - - +\n| = = |\n+ - - +\n", render(&canvas, &parse_options(args("-f =")).unwrap().charset));
+    }
 
-fn main () {
+    #[test]
+    fn test_render_combined_drawing() {
+        let shapes = parse_shapes(&args("line 0,0 2,0 line 0,0 0,2"), None).unwrap();
+        let drawing = resize(combine_shapes(shapes).unwrap(), Some(4), None);
+        let canvas = plot(Box::new(drawing)).unwrap();
 
+        assert_eq!("|      \n|      \n+ - -  \n", render(&canvas, &parse_charset("ascii").unwrap()));
+    }
 }
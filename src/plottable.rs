@@ -0,0 +1,232 @@
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use junction::{join, Arms};
+use raster::Raster;
+use style::Style;
+
+// -- Plottable objects --
+pub trait Plottable {
+    // @TODO it's not really clear that get_dimensions() returns the canvas size needed for the object, not the *actual* dimensions for the object
+// a horizontal line at (1, 1) to (3, 1) has the canvas size (4, 2) and dimension is actually (3, 1). get_dimensions at the moment will return (4, 2)
+    fn get_dimensions(&self) -> Dimensions;
+
+    // the bottom left corner of the canvas needed for the object, get_dimensions() is measured from here.
+    // this is (0, 0) unless the object extends into negative space
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.get_coords())
+    }
+
+    fn get_coords(&self) -> Vec<Coords>;
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation>;
+
+    // the object as paths, rotated objects are rasterized again from their paths so lines stay connected.
+    // by default every coord is a path of its own
+    fn get_paths(&self) -> Vec<Path> {
+        self.get_coords().into_iter()
+            .filter_map(|coords| self.get_representation_at(coords).map(|representation| Path(representation, vec![coords])))
+            .collect()
+    }
+
+    // the paths with the style each of them is drawn in, so transformed objects keep the styles of the objects
+    // contained in them. None for paths drawn without one
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        self.get_paths().into_iter().map(|path| (path, None)).collect()
+    }
+
+    // the style of the whole object, see Styled
+    fn get_style(&self) -> Option<Style> {
+        None
+    }
+
+    // writes the object into the raster, objects should override this if looking up the representation
+    // of every single coord is expensive
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.get_coords() {
+            if let Some(representation) = self.get_representation_at(coords) {
+                raster.set(coords, representation);
+            }
+        }
+    }
+}
+
+impl<P: Plottable + ?Sized> Plottable for Box<P> {
+    fn get_dimensions(&self) -> Dimensions {
+        (**self).get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        (**self).get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        (**self).get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        (**self).get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        (**self).get_paths()
+    }
+
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        (**self).get_styled_paths()
+    }
+
+    fn get_style(&self) -> Option<Style> {
+        (**self).get_style()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        (**self).rasterize(raster)
+    }
+}
+
+pub struct CombinedObject(pub Dimensions, pub Vec<Box<dyn Plottable>>);
+
+impl Plottable for CombinedObject {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.1.iter()
+            .map(|contained_plottable| contained_plottable.get_origin())
+            .fold(Coords(0, 0), |Coords(x0, y0), Coords(x1, y1)| Coords(x0.min(x1), y0.min(y1)))
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        let mut coords = vec![];
+
+        for contained_plottable in self.1.iter() {
+            coords.extend(contained_plottable.get_coords());
+        }
+
+        Coords::unique(coords)
+    }
+
+    // the representation of the first contained object, joined with the lines of the others like Raster does
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        let representations = self.get_contained_representations_at(coords);
+        let first = representations.first()?;
+
+        let arms = representations.iter().map(Arms::of).fold(Arms::default(), |arms, other| arms | other);
+
+        if representations.iter().filter(|representation| !Arms::of(representation).is_empty()).count() < 2 || Arms::of(first).is_empty() {
+            return Some(first.clone());
+        }
+
+        let arms_at = |neighbour| self.get_contained_representations_at(neighbour).iter().map(Arms::of).fold(Arms::default(), |arms, other| arms | other);
+        Some(join(coords, first, arms, arms_at))
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.1.iter().flat_map(|contained_plottable| contained_plottable.get_paths()).collect()
+    }
+
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        self.1.iter().flat_map(|contained_plottable| contained_plottable.get_styled_paths()).collect()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for contained_plottable in self.1.iter() {
+            contained_plottable.rasterize(raster);
+        }
+    }
+}
+
+impl CombinedObject {
+    fn get_contained_representations_at(&self, coords: Coords) -> Vec<Representation> {
+        self.1.iter()
+            .filter(|contained_plottable| contained_plottable.get_coords().contains(&coords))
+            .filter_map(|contained_plottable| contained_plottable.get_representation_at(coords))
+            .collect()
+    }
+}
+
+// -- functions --
+pub fn combine<T: Plottable + 'static, U: Plottable + 'static>(a: Box<T>, b: Box<U>) -> Result<CombinedObject, Error> {
+    let contained_objects: Vec<Box<dyn Plottable>> = vec![a, b];
+
+    let mut contained_coords = vec![];
+    contained_coords.extend(contained_objects[0].get_coords().iter());
+    contained_coords.extend(contained_objects[1].get_coords().iter());
+
+    if contained_coords.is_empty() {
+        return Err(Error::EmptyShape);
+    }
+
+    let dimensions = Dimensions::from_coords(&contained_coords);
+    Ok(CombinedObject(dimensions, contained_objects))
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+    use fixtures::*;
+    use line::Line;
+    use rectangle::Rectangle;
+
+    #[test]
+    fn test_combine_expands_dimensions_to_fit_largest_object_line() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let line_2 = fixtures_horizontal_line();
+
+        // combined object is supposed to be large enough to contain 2 lines
+        let lines_combined = combine(Box::new(line_1), Box::new(line_2)).unwrap();
+
+        assert_eq!((lines_combined.0).0, 3);
+        assert_eq!((lines_combined.0).1, 3);
+
+        assert_eq!(lines_combined.1.len(), 2);
+    }
+
+    #[test]
+    fn test_combine_expands_dimensions_to_fit_largest_object_circle() {
+        let line_1 = fixtures_diagonal_line_ltr();
+        let circle_1 = fixtures_circle();
+
+        // combined object is supposed to be large enough to contain 2 lines
+        let lines_combined = combine(Box::new(line_1), Box::new(circle_1)).unwrap();
+
+        assert_eq!((lines_combined.0).0, 3);
+        assert_eq!((lines_combined.0).1, 3);
+
+        assert_eq!(lines_combined.1.len(), 2);
+    }
+
+    #[test]
+    fn test_combined_lines_are_joined() {
+        let horizontal = Line::new(Coords(0, 1), Coords(4, 1)).unwrap();
+        let vertical = Line::new(Coords(2, 0), Coords(2, 1)).unwrap();
+        let combined = combine(Box::new(horizontal), Box::new(vertical)).unwrap();
+
+        assert_eq!(Some(Representation::TeeDown), combined.get_representation_at(Coords(2, 1)));
+        assert_eq!(Some(Representation::HorizontalLine), combined.get_representation_at(Coords(1, 1)));
+
+        let canvas = plot(Box::new(combined)).unwrap();
+        assert_eq!("─ ─ ┬ ─ ─\n    │    \n", render(&canvas, &Charset::unicode()));
+        assert_eq!("- - + - -\n    |    \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_grid_of_rectangles() {
+        let left = Rectangle::new(Coords(0, 0), Coords(2, 2)).unwrap();
+        let right = Rectangle::new(Coords(2, 0), Coords(4, 2)).unwrap();
+        let canvas = plot(Box::new(combine(Box::new(left), Box::new(right)).unwrap())).unwrap();
+
+        assert_eq!("┌ ─ ┬ ─ ┐\n│   │   │\n└ ─ ┴ ─ ┘\n", render(&canvas, &Charset::unicode()));
+    }
+
+    #[test]
+    fn test_combine_empty_objects() {
+        let empty_1 = CombinedObject(Dimensions(0, 0), vec![]);
+        let empty_2 = CombinedObject(Dimensions(0, 0), vec![]);
+
+        assert_eq!(Err(Error::EmptyShape), combine(Box::new(empty_1), Box::new(empty_2)).map(|_| ()));
+    }
+}
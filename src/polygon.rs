@@ -0,0 +1,254 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use fill::Filling;
+use line::Line;
+use plottable::Plottable;
+use raster::Raster;
+
+// -- structs --
+// consecutive vertices connected by lines, the vertices are drawn first so they win over the lines
+#[derive(Debug)]
+pub struct Polyline {
+    dimensions: Dimensions,
+    vertices: Vec<Coords>,
+    coords: Vec<Coords>,
+    representations: HashMap<Coords, Representation>,
+    paths: Vec<Path>,
+}
+
+// a polyline whose last vertex is connected to the first one
+#[derive(Debug)]
+pub struct Polygon(Polyline);
+
+impl Polyline {
+    pub fn new(vertices: Vec<Coords>) -> Result<Polyline, Error> {
+        Polyline::trace(vertices, false)
+    }
+
+    fn trace(mut vertices: Vec<Coords>, closed: bool) -> Result<Polyline, Error> {
+        for vertex in vertices.iter() {
+            vertex.check_range()?;
+        }
+
+        vertices.dedup();
+
+        if closed && vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+
+        if vertices.len() < if closed { 3 } else { 2 } {
+            return Err(Error::TooFewVertices(vertices.len()));
+        }
+
+        let mut segments = vec![];
+
+        for (i, start) in vertices.iter().enumerate() {
+            let end = match vertices.get(i + 1) {
+                Some(end) => end,
+                None if closed => &vertices[0],
+                None => break,
+            };
+
+            let line = Line::new(*start, *end)?;
+            segments.push((Path(Line::get_line_representation(*start, *end), vec![*start, *end]), line));
+        }
+
+        let mut paths = vec![];
+
+        for (i, vertex) in vertices.iter().enumerate() {
+            let incoming = if i > 0 { segments.get(i - 1) } else if closed { segments.last() } else { None };
+            let outgoing = segments.get(i);
+
+            let representation = match (incoming, outgoing) {
+                (Some((incoming, _)), Some((outgoing, _))) => Polyline::get_vertex_representation(*vertex, incoming, outgoing),
+                (Some((Path(representation, _), _)), None) | (None, Some((Path(representation, _), _))) => representation.clone(),
+                (None, None) => continue,
+            };
+
+            paths.push(Path(representation, vec![*vertex]));
+        }
+
+        let mut coords = vec![];
+        let mut representations = HashMap::new();
+
+        // the lines keep the glyph of every cell, see Line
+        let cells = paths.iter()
+            .map(|Path(representation, path)| (path[0], representation.clone()))
+            .chain(segments.iter().flat_map(|(_, line)| line.get_cells().map(|(cell, representation)| (*cell, representation.clone()))));
+
+        for (cell, representation) in cells {
+            if let Entry::Vacant(entry) = representations.entry(cell) {
+                entry.insert(representation);
+                coords.push(cell);
+            }
+        }
+
+        paths.extend(segments.into_iter().map(|(path, _)| path));
+
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(Polyline { dimensions, vertices, coords, representations, paths })
+    }
+
+    // two lines meeting at a right angle form a corner, lines continuing in the same direction stay a line.
+    // anything else is drawn as a plain vertex
+    fn get_vertex_representation(vertex: Coords, incoming: &Path, outgoing: &Path) -> Representation {
+        let Path(incoming_representation, incoming_path) = incoming;
+        let Path(outgoing_representation, outgoing_path) = outgoing;
+
+        let arm = |Coords(x, y): Coords| (f64::from((x - vertex.0).signum()), f64::from((y - vertex.1).signum()));
+        let (dx0, dy0) = arm(incoming_path[0]);
+        let (dx1, dy1) = arm(outgoing_path[1]);
+
+        match (incoming_representation, outgoing_representation) {
+            (&Representation::HorizontalLine, &Representation::VerticalLine) | (&Representation::VerticalLine, &Representation::HorizontalLine) => {
+                Representation::corner_from_direction(dx0 + dx1, dy0 + dy1, false)
+            },
+            _ if incoming_representation == outgoing_representation && (dx0, dy0) == (-dx1, -dy1) => incoming_representation.clone(),
+            _ => Representation::Vertex,
+        }
+    }
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Coords>) -> Result<Polygon, Error> {
+        Ok(Polygon(Polyline::trace(vertices, true)?))
+    }
+
+    // fills the inside with Representation::Fill, parts of a polygon crossing itself are filled by the even-odd rule
+    pub fn filled(mut self) -> Polygon {
+        let filling = Filling::new(&(self.0).vertices, &(self.0).coords);
+
+        for coords in filling.get_coords() {
+            if let Entry::Vacant(entry) = (self.0).representations.entry(*coords) {
+                entry.insert(Representation::Fill);
+                (self.0).coords.push(*coords);
+            }
+        }
+
+        (self.0).paths.extend_from_slice(filling.get_paths());
+        self
+    }
+}
+
+impl Plottable for Polyline {
+    fn get_dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&self.coords)
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.coords.clone()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.representations.get(&coords).cloned()
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.paths.clone()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in self.coords.iter() {
+            raster.set(*coords, self.representations[coords].clone());
+        }
+    }
+}
+
+impl Plottable for Polygon {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0.get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.0.get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.0.get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.0.get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.0.get_paths()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        self.0.rasterize(raster)
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+
+    #[test]
+    fn test_new_polyline() {
+        let polyline = Polyline::new(vec![Coords(0, 0), Coords(2, 0), Coords(2, 2)]).unwrap();
+
+        assert_eq!(Dimensions(3, 3), polyline.get_dimensions());
+        assert_eq!(5, polyline.get_coords().len());
+        assert_eq!(Some(Representation::HorizontalLine), polyline.get_representation_at(Coords(0, 0)));
+        assert_eq!(Some(Representation::BottomRightCorner), polyline.get_representation_at(Coords(2, 0)));
+        assert_eq!(Some(Representation::VerticalLine), polyline.get_representation_at(Coords(2, 2)));
+    }
+
+    #[test]
+    fn test_render_arrow() {
+        let arrow = Polyline::new(vec![Coords(0, 2), Coords(2, 0), Coords(4, 2)]).unwrap();
+        let canvas = plot(Box::new(arrow)).unwrap();
+
+        assert_eq!("\\       /\n  \\   /  \n    +    \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_polygon() {
+        let triangle = Polygon::new(vec![Coords(0, 0), Coords(4, 0), Coords(0, 4)]).unwrap();
+        let canvas = plot(Box::new(triangle)).unwrap();
+
+        assert_eq!("+        \n| \\      \n|   \\    \n|     \\  \n+ - - - +\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_render_filled_polygon() {
+        let triangle = Polygon::new(vec![Coords(0, 0), Coords(4, 0), Coords(0, 4)]).unwrap().filled();
+        let canvas = plot(Box::new(triangle)).unwrap();
+
+        assert_eq!("+        \n| \\      \n| # \\    \n| # # \\  \n+ - - - +\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_polygon_is_closed_once() {
+        let square = Polygon::new(vec![Coords(0, 0), Coords(0, 2), Coords(2, 2), Coords(2, 0), Coords(0, 0)]).unwrap();
+
+        // 4 vertices and 4 segments
+        assert_eq!(8, square.get_paths().len());
+
+        let canvas = plot(Box::new(square)).unwrap();
+        assert_eq!("┌ ─ ┐\n│   │\n└ ─ ┘\n", render(&canvas, &Charset::unicode()));
+    }
+
+    #[test]
+    fn test_collinear_vertex_stays_a_line() {
+        let polyline = Polyline::new(vec![Coords(0, 0), Coords(2, 0), Coords(4, 0)]).unwrap();
+
+        assert_eq!(Some(Representation::HorizontalLine), polyline.get_representation_at(Coords(2, 0)));
+    }
+
+    #[test]
+    fn test_too_few_vertices() {
+        assert_eq!(Err(Error::TooFewVertices(1)), Polyline::new(vec![Coords(1, 1), Coords(1, 1)]).map(|_| ()));
+        assert_eq!(Err(Error::TooFewVertices(2)), Polygon::new(vec![Coords(0, 0), Coords(1, 1), Coords(0, 0)]).map(|_| ()));
+    }
+}
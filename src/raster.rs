@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use canvas::{Canvas, CoordinateSystem};
+use coords::{Coords, Dimensions, PlottedCoords, Representation};
+use junction::{join, Arms};
+use style::Style;
+
+// -- structs --
+// the cells and the arms of the junctions in them, a cell without a junction has no arms. dense cells keep
+// their arms right next to them
+enum Cells {
+    Dense(Vec<(Option<Representation>, Arms)>),
+    Sparse(HashMap<Coords, Representation>, HashMap<Coords, Arms>),
+}
+
+// grid buffer each Plottable writes its cells into once, see Plottable::rasterize().
+// the raster covers `Dimensions` starting at the origin, which is translated to (0, 0) on the canvas.
+// cells where lines of several objects meet are collected with the arms of all of them. cells are drawn
+// in the current style, which is kept for every cell drawn in a style
+pub struct Raster {
+    origin: Coords,
+    dimensions: Dimensions,
+    cells: Cells,
+    style: Option<Style>,
+    styles: HashMap<Coords, Style>,
+}
+
+impl Raster {
+    pub fn new(origin: Coords, dimensions: Dimensions) -> Raster {
+        let Dimensions(width, height) = dimensions;
+        let size = width as usize * height as usize;
+
+        Raster { origin, dimensions, cells: Cells::Dense(vec![(None, Arms::default()); size]), style: None, styles: HashMap::new() }
+    }
+
+    pub fn sparse(origin: Coords, dimensions: Dimensions) -> Raster {
+        Raster { origin, dimensions, cells: Cells::Sparse(HashMap::new(), HashMap::new()), style: None, styles: HashMap::new() }
+    }
+
+    // the style of the cells set from now on, returns the previous style so it can be restored
+    pub fn set_style(&mut self, style: Option<Style>) -> Option<Style> {
+        ::std::mem::replace(&mut self.style, style)
+    }
+
+    pub fn get_origin(&self) -> Coords {
+        self.origin
+    }
+
+    pub fn get_dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    pub fn get(&self, coords: Coords) -> Option<&Representation> {
+        let index = self.index_of(coords)?;
+
+        match self.cells {
+            Cells::Dense(ref cells) => cells[index].0.as_ref(),
+            Cells::Sparse(ref cells, _) => cells.get(&coords),
+        }
+    }
+
+    // the first representation written to a cell wins, just like CombinedObject::get_representation_at()
+    // returns the representation of the first contained object. lines meeting lines are joined into
+    // junctions when the canvas is created. coords outside of the raster are ignored
+    pub fn set(&mut self, coords: Coords, representation: Representation) {
+        let index = match self.index_of(coords) {
+            Some(index) => index,
+            None => return,
+        };
+
+        let style = &self.style;
+        let styles = &mut self.styles;
+
+        let existing_arms = match self.cells {
+            Cells::Dense(ref mut cells) => match cells[index].0 {
+                Some(ref existing) => Arms::of(existing),
+                None => {
+                    cells[index].0 = Some(representation);
+                    if let Some(ref style) = *style { styles.insert(coords, style.clone()); }
+                    return;
+                },
+            },
+            Cells::Sparse(ref mut cells, _) => match cells.get(&coords) {
+                Some(existing) => Arms::of(existing),
+                None => {
+                    cells.insert(coords, representation);
+                    if let Some(ref style) = *style { styles.insert(coords, style.clone()); }
+                    return;
+                },
+            },
+        };
+
+        let arms = Arms::of(&representation);
+
+        // a line drawn over a line going the same way adds nothing to join
+        if existing_arms.is_empty() || arms.is_empty() || existing_arms | arms == existing_arms {
+            return;
+        }
+
+        let junction = match self.cells {
+            Cells::Dense(ref mut cells) => &mut cells[index].1,
+            Cells::Sparse(_, ref mut junctions) => junctions.entry(coords).or_default(),
+        };
+
+        *junction = existing_arms | *junction | arms;
+    }
+
+    // the arms of a junction are kept when it is joined, so the cells can be joined in place
+    fn join_junctions(&mut self) {
+        let Coords(origin_x, origin_y) = self.origin;
+        let Dimensions(width, height) = self.dimensions;
+
+        match self.cells {
+            Cells::Dense(ref mut cells) => {
+                let arms_at = |cells: &[(Option<Representation>, Arms)], Coords(x, y): Coords| {
+                    let (x, y) = (x - origin_x, y - origin_y);
+
+                    if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+                        return Arms::default();
+                    }
+
+                    get_dense_arms(&cells[y as usize * width as usize + x as usize])
+                };
+
+                for index in 0..cells.len() {
+                    let arms = cells[index].1;
+
+                    if arms.is_empty() {
+                        continue;
+                    }
+
+                    let coords = Coords(origin_x + (index % width as usize) as i32, origin_y + (index / width as usize) as i32);
+                    let joined = join(coords, cells[index].0.as_ref().unwrap(), arms, |neighbour| arms_at(cells, neighbour));
+
+                    cells[index].0 = Some(joined);
+                }
+            },
+            Cells::Sparse(ref mut cells, ref junctions) => {
+                let joined = junctions.iter()
+                    .map(|(&coords, &arms)| (coords, join(coords, &cells[&coords], arms, |neighbour| get_sparse_arms(cells, junctions, neighbour))))
+                    .collect::<Vec<_>>();
+
+                cells.extend(joined);
+            },
+        }
+    }
+
+    fn index_of(&self, coords: Coords) -> Option<usize> {
+        let (x, y) = self.to_canvas_coords(coords)?;
+        let Dimensions(width, _) = self.dimensions;
+
+        Some(y as usize * width as usize + x as usize)
+    }
+
+    fn to_canvas_coords(&self, coords: Coords) -> Option<(u32, u32)> {
+        let Coords(origin_x, origin_y) = self.origin;
+        let Dimensions(width, height) = self.dimensions;
+
+        let x = i64::from(coords.0) - i64::from(origin_x);
+        let y = i64::from(coords.1) - i64::from(origin_y);
+
+        if x >= 0 && y >= 0 && x < i64::from(width) && y < i64::from(height) { Some((x as u32, y as u32)) } else { None }
+    }
+
+    // dense rasters produce a canvas with every cell, sparse rasters only the drawn cells
+    pub fn into_canvas(mut self) -> Canvas {
+        self.join_junctions();
+
+        let dimensions = self.dimensions;
+        let Dimensions(width, height) = dimensions;
+        let Coords(origin_x, origin_y) = self.origin;
+
+        // most drawings have no styles, which saves looking up every cell
+        let styles = &self.styles;
+        let style_at = |x: u32, y: u32| if styles.is_empty() { None } else { styles.get(&Coords((i64::from(origin_x) + i64::from(x)) as i32, (i64::from(origin_y) + i64::from(y)) as i32)).cloned() };
+
+        let canvas_coords = match self.cells {
+            Cells::Dense(mut cells) => {
+                let mut canvas_coords = Vec::with_capacity(cells.len());
+
+                for y in (0..height).rev() {
+                    for x in 0..width {
+                        let index = y as usize * width as usize + x as usize;
+                        let representation = cells[index].0.take().unwrap_or(Representation::Canvas);
+
+                        canvas_coords.push(PlottedCoords(x, y, representation, style_at(x, y)));
+                    }
+                }
+
+                canvas_coords
+            },
+            Cells::Sparse(ref cells, _) => {
+                let mut canvas_coords = cells.iter()
+                    .filter(|&(_, representation)| *representation != Representation::Canvas)
+                    .filter_map(|(coords, representation)| {
+                        self.to_canvas_coords(*coords).map(|(x, y)| PlottedCoords(x, y, representation.clone(), style_at(x, y)))
+                    })
+                    .collect::<Vec<_>>();
+
+                canvas_coords.sort_by_key(|&PlottedCoords(x, y, _, _)| CoordinateSystem::Cartesian.get_sort_key(x, y));
+                canvas_coords
+            },
+        };
+
+        Canvas(dimensions, canvas_coords, CoordinateSystem::Cartesian)
+    }
+}
+
+// -- functions --
+// the arms of the junction in a dense cell, or of its representation if there is none
+fn get_dense_arms(cell: &(Option<Representation>, Arms)) -> Arms {
+    match *cell {
+        (ref representation, junction) if junction.is_empty() => representation.as_ref().map(Arms::of).unwrap_or_default(),
+        (_, junction) => junction,
+    }
+}
+
+fn get_sparse_arms(cells: &HashMap<Coords, Representation>, junctions: &HashMap<Coords, Arms>, coords: Coords) -> Arms {
+    junctions.get(&coords).copied().unwrap_or_else(|| cells.get(&coords).map(Arms::of).unwrap_or_default())
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_representation_wins() {
+        for mut raster in [Raster::new(Coords(0, 0), Dimensions(2, 2)), Raster::sparse(Coords(0, 0), Dimensions(2, 2))] {
+            raster.set(Coords(1, 0), Representation::Circle);
+            raster.set(Coords(1, 0), Representation::HorizontalLine);
+
+            assert_eq!(Some(&Representation::Circle), raster.get(Coords(1, 0)));
+            assert_eq!(None, raster.get(Coords(0, 0)));
+        }
+    }
+
+    #[test]
+    fn test_lines_are_joined() {
+        for mut raster in [Raster::new(Coords(0, 0), Dimensions(3, 3)), Raster::sparse(Coords(0, 0), Dimensions(3, 3))] {
+            for x in 0..3 {
+                raster.set(Coords(x, 1), Representation::HorizontalLine);
+            }
+
+            for y in 1..3 {
+                raster.set(Coords(1, y), Representation::VerticalLine);
+            }
+
+            raster.set(Coords(1, 1), Representation::Circle);
+
+            let canvas = raster.into_canvas();
+
+            assert_eq!(Representation::TeeUp, canvas.get_representation_at(Coords(1, 1)));
+            assert_eq!(Representation::VerticalLine, canvas.get_representation_at(Coords(1, 2)));
+        }
+    }
+
+    #[test]
+    fn test_overlapping_lines_stay_lines() {
+        for mut raster in [Raster::new(Coords(0, 0), Dimensions(3, 1)), Raster::sparse(Coords(0, 0), Dimensions(3, 1))] {
+            for _ in 0..2 {
+                for x in 0..3 {
+                    raster.set(Coords(x, 0), Representation::HorizontalLine);
+                }
+            }
+
+            assert_eq!(Representation::HorizontalLine, raster.into_canvas().get_representation_at(Coords(1, 0)));
+        }
+    }
+
+    #[test]
+    fn test_coords_outside_are_ignored() {
+        let mut raster = Raster::new(Coords(0, 0), Dimensions(2, 2));
+        raster.set(Coords(2, 0), Representation::Circle);
+        raster.set(Coords(0, 5), Representation::Circle);
+
+        assert_eq!(None, raster.get(Coords(2, 0)));
+        assert!(raster.into_canvas().1.iter().all(|plotted_coords| plotted_coords.2 == Representation::Canvas));
+    }
+
+    #[test]
+    fn test_into_canvas() {
+        let mut dense = Raster::new(Coords(0, 0), Dimensions(2, 2));
+        let mut sparse = Raster::sparse(Coords(0, 0), Dimensions(2, 2));
+
+        for raster in [&mut dense, &mut sparse] {
+            raster.set(Coords(0, 0), Representation::HorizontalLine);
+            raster.set(Coords(1, 1), Representation::Circle);
+        }
+
+        assert_eq!(dense.into_canvas().1, vec![
+            PlottedCoords(0, 1, Representation::Canvas, None),
+            PlottedCoords(1, 1, Representation::Circle, None),
+            PlottedCoords(0, 0, Representation::HorizontalLine, None),
+            PlottedCoords(1, 0, Representation::Canvas, None),
+        ]);
+
+        assert_eq!(sparse.into_canvas().1, vec![
+            PlottedCoords(1, 1, Representation::Circle, None),
+            PlottedCoords(0, 0, Representation::HorizontalLine, None),
+        ]);
+    }
+
+    #[test]
+    fn test_origin_is_translated_to_canvas_origin() {
+        for mut raster in [Raster::new(Coords(-1, -2), Dimensions(2, 3)), Raster::sparse(Coords(-1, -2), Dimensions(2, 3))] {
+            raster.set(Coords(-1, -2), Representation::Circle);
+            raster.set(Coords(0, 0), Representation::HorizontalLine);
+            raster.set(Coords(1, 0), Representation::HorizontalLine);
+            raster.set(Coords(-2, 0), Representation::HorizontalLine);
+
+            assert_eq!(Some(&Representation::Circle), raster.get(Coords(-1, -2)));
+
+            let canvas = raster.into_canvas();
+
+            assert_eq!(Representation::HorizontalLine, canvas.get_representation_at(Coords(1, 2)));
+            assert_eq!(Representation::Circle, canvas.get_representation_at(Coords(0, 0)));
+            assert_eq!(2, canvas.1.iter().filter(|plotted_coords| plotted_coords.2 != Representation::Canvas).count());
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use plottable::Plottable;
+
+// -- structs --
+#[derive(Debug)]
+pub struct Rectangle {
+    dimensions: Dimensions,
+    bottom_left: Coords,
+    top_right: Coords,
+    rounded: bool,
+    filled: bool,
+}
+
+impl Rectangle {
+    // the rectangle spanning both corners, any two opposite corners work
+    pub fn new(corner: Coords, opposite_corner: Coords) -> Result<Rectangle, Error> {
+        let Coords(x0, y0) = corner.check_range()?;
+        let Coords(x1, y1) = opposite_corner.check_range()?;
+
+        let bottom_left = Coords(x0.min(x1), y0.min(y1));
+        let top_right = Coords(x0.max(x1), y0.max(y1));
+
+        Ok(Rectangle {
+            dimensions: Dimensions::from_coords(&[bottom_left, top_right]),
+            bottom_left,
+            top_right,
+            rounded: false,
+            filled: false,
+        })
+    }
+
+    pub fn rounded(mut self) -> Rectangle {
+        self.rounded = true;
+        self
+    }
+
+    pub fn filled(mut self) -> Rectangle {
+        self.filled = true;
+        self
+    }
+
+    fn get_corners(&self) -> [(Coords, Representation); 4] {
+        let Coords(left, bottom) = self.bottom_left;
+        let Coords(right, top) = self.top_right;
+
+        [
+            (Coords(left, top), Representation::corner_from_direction(1.0, -1.0, self.rounded)),
+            (Coords(right, top), Representation::corner_from_direction(-1.0, -1.0, self.rounded)),
+            (Coords(left, bottom), Representation::corner_from_direction(1.0, 1.0, self.rounded)),
+            (Coords(right, bottom), Representation::corner_from_direction(-1.0, 1.0, self.rounded)),
+        ]
+    }
+}
+
+impl Plottable for Rectangle {
+    fn get_dimensions(&self) -> Dimensions {
+        self.dimensions
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&[self.bottom_left])
+    }
+
+    // top to bottom, left to right
+    fn get_coords(&self) -> Vec<Coords> {
+        let Coords(left, bottom) = self.bottom_left;
+        let Coords(right, top) = self.top_right;
+
+        let mut coords = vec![];
+
+        for y in (bottom..=top).rev() {
+            if self.filled || y == bottom || y == top {
+                coords.extend((left..=right).map(|x| Coords(x, y)));
+            } else {
+                coords.push(Coords(left, y));
+
+                if right != left {
+                    coords.push(Coords(right, y));
+                }
+            }
+        }
+
+        coords
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        let Coords(x, y) = coords;
+        let Coords(left, bottom) = self.bottom_left;
+        let Coords(right, top) = self.top_right;
+
+        if x < left || x > right || y < bottom || y > top {
+            return None;
+        }
+
+        let on_vertical_edge = x == left || x == right;
+        let on_horizontal_edge = y == bottom || y == top;
+
+        // a rectangle which is only a single row or column is drawn as a line
+        if bottom == top {
+            Some(Representation::HorizontalLine)
+        } else if left == right {
+            Some(Representation::VerticalLine)
+        } else if on_vertical_edge && on_horizontal_edge {
+            self.get_corners().iter().find(|&&(corner, _)| corner == coords).map(|(_, representation)| representation.clone())
+        } else if on_horizontal_edge {
+            Some(Representation::HorizontalLine)
+        } else if on_vertical_edge {
+            Some(Representation::VerticalLine)
+        } else if self.filled {
+            Some(Representation::Fill)
+        } else {
+            None
+        }
+    }
+
+    // corners first so they win over the edges when the paths are rasterized again
+    fn get_paths(&self) -> Vec<Path> {
+        let Coords(left, bottom) = self.bottom_left;
+        let Coords(right, top) = self.top_right;
+
+        if bottom == top || left == right {
+            let representation = if bottom == top { Representation::HorizontalLine } else { Representation::VerticalLine };
+            return vec![Path(representation, vec![self.bottom_left, self.top_right])];
+        }
+
+        let mut paths = self.get_corners().iter()
+            .map(|&(coords, ref representation)| Path(representation.clone(), vec![coords]))
+            .collect::<Vec<_>>();
+
+        paths.push(Path(Representation::HorizontalLine, vec![Coords(left, top), Coords(right, top)]));
+        paths.push(Path(Representation::VerticalLine, vec![Coords(right, top), Coords(right, bottom)]));
+        paths.push(Path(Representation::HorizontalLine, vec![Coords(right, bottom), Coords(left, bottom)]));
+        paths.push(Path(Representation::VerticalLine, vec![Coords(left, bottom), Coords(left, top)]));
+
+        // the fill is the closed outline, which is filled again when the paths are rasterized
+        if self.filled {
+            paths.push(Path(Representation::Fill, vec![Coords(left, top), Coords(right, top), Coords(right, bottom), Coords(left, bottom), Coords(left, top)]));
+        }
+
+        paths
+    }
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+    use coords::MAX_COORD;
+
+    #[test]
+    fn test_new_rectangle() {
+        let rectangle = Rectangle::new(Coords(3, 2), Coords(0, 0)).unwrap();
+
+        assert_eq!(Dimensions(4, 3), rectangle.get_dimensions());
+        assert_eq!(10, rectangle.get_coords().len());
+        assert_eq!(Some(Representation::TopLeftCorner), rectangle.get_representation_at(Coords(0, 2)));
+        assert_eq!(Some(Representation::BottomRightCorner), rectangle.get_representation_at(Coords(3, 0)));
+        assert_eq!(Some(Representation::VerticalLine), rectangle.get_representation_at(Coords(3, 1)));
+        assert_eq!(None, rectangle.get_representation_at(Coords(1, 1)));
+    }
+
+    #[test]
+    fn test_render_rectangle() {
+        let canvas = plot(Box::new(Rectangle::new(Coords(0, 0), Coords(3, 2)).unwrap())).unwrap();
+
+        assert_eq!("+ - - +\n|     |\n+ - - +\n", render(&canvas, &Charset::ascii()));
+        assert_eq!("┌ ─ ─ ┐\n│     │\n└ ─ ─ ┘\n", render(&canvas, &Charset::unicode()));
+    }
+
+    #[test]
+    fn test_render_rounded_filled_rectangle() {
+        let rectangle = Rectangle::new(Coords(-1, -1), Coords(2, 1)).unwrap().rounded().filled();
+        let canvas = plot(Box::new(rectangle)).unwrap();
+
+        assert_eq!(". - - .\n| # # |\n' - - '\n", render(&canvas, &Charset::ascii()));
+        assert_eq!("╭ ─ ─ ╮\n│ █ █ │\n╰ ─ ─ ╯\n", render(&canvas, &Charset::unicode()));
+    }
+
+    #[test]
+    fn test_rectangle_of_a_single_row() {
+        let rectangle = Rectangle::new(Coords(0, 1), Coords(2, 1)).unwrap();
+
+        assert_eq!(vec![Coords(0, 1), Coords(1, 1), Coords(2, 1)], rectangle.get_coords());
+        assert_eq!(Some(Representation::HorizontalLine), rectangle.get_representation_at(Coords(0, 1)));
+    }
+
+    #[test]
+    fn test_new_rectangle_out_of_range() {
+        assert_eq!(Err(Error::CoordsOutOfRange(Coords(0, MAX_COORD + 1))), Rectangle::new(Coords(0, 0), Coords(0, MAX_COORD + 1)).map(|_| ()));
+    }
+}
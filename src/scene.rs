@@ -0,0 +1,783 @@
+use std::error;
+use std::fmt;
+
+use bezier::{CubicBezier, QuadraticBezier};
+use canvas::{Charset, CoordinateSystem, RenderOptions};
+use circle::Circle;
+use ellipse::{Arc, Ellipse};
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use line::Line;
+use plottable::{CombinedObject, Plottable};
+use raster::Raster;
+use polygon::{Polygon, Polyline};
+use rectangle::Rectangle;
+use style::{Color, Style, Styled};
+
+// A scene is a small TOML subset: an optional [canvas] table and one [[shape]] table per shape.
+//
+//     [canvas]
+//     width = 20
+//     y_axis = "down" # y = 0 is the top row, see CoordinateSystem
+//
+//     [[shape]]
+//     type = "line"
+//     from = [0, 0]
+//     to = [5, 5]
+//
+//     [[shape]]
+//     type = "circle"
+//     center = [3, 3]
+//     radius = 2
+//     round = true # stretched by the aspect ratio of the cells, see parse_scene_with()
+//
+//     [[shape]]
+//     type = "rectangle"
+//     from = [0, 0]
+//     to = [6, 4]
+//     rounded = true
+//
+//     [[shape]]
+//     type = "polygon"
+//     points = [0, 0, 4, 0, 2, 3] # x and y of every vertex
+//     filled = true
+//
+// Every shape can have a style: `color` and `background` are a color name like "red" or "bright-red",
+// "#rrggbb" or an index into the 256 color palette, `bold` and `dim` are booleans. `glyph` is a single
+// character the whole shape is drawn with and `charset` the name of a charset like "heavy" used for the shape.
+
+const STYLE_KEYS: [&str; 6] = ["color", "background", "bold", "dim", "glyph", "charset"];
+
+// -- structs --
+#[derive(Debug, PartialEq)]
+pub struct SceneError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl SceneError {
+    fn new(line: usize, column: usize, message: String) -> SceneError {
+        SceneError { line, column, message }
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl error::Error for SceneError {}
+
+// the shapes of a scene and the coordinate system they are meant to be plotted in
+pub struct Scene(pub CombinedObject, pub CoordinateSystem);
+
+impl Scene {
+    pub fn get_coordinate_system(&self) -> CoordinateSystem {
+        self.1
+    }
+}
+
+impl Plottable for Scene {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0.get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.0.get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.0.get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.0.get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.0.get_paths()
+    }
+
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        self.0.get_styled_paths()
+    }
+
+    fn get_style(&self) -> Option<Style> {
+        self.0.get_style()
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        self.0.rasterize(raster)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Integer(i64),
+    Boolean(bool),
+    Str(String),
+    // elements are stored with their column
+    Array(Vec<(i64, usize)>),
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    value: Value,
+    line: usize,
+    column: usize,
+    value_column: usize,
+}
+
+#[derive(Debug)]
+struct Table {
+    name: String,
+    line: usize,
+    column: usize,
+    entries: Vec<Entry>,
+}
+
+impl Table {
+    fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    fn require(&self, key: &str, context: &str) -> Result<&Entry, SceneError> {
+        self.get(key).ok_or_else(|| SceneError::new(self.line, self.column, format!("missing key '{}' for {}", key, context)))
+    }
+
+    fn error(&self, error: Error) -> SceneError {
+        SceneError::new(self.line, self.column, error.to_string())
+    }
+
+    // the optional `filled` key of closed shapes
+    fn is_filled(&self) -> Result<bool, SceneError> {
+        match self.get("filled") {
+            Some(entry) => entry.as_bool(),
+            None => Ok(false),
+        }
+    }
+
+    // the optional style keys of shapes
+    fn get_style(&self) -> Result<Option<Style>, SceneError> {
+        let mut style = Style::new();
+
+        if let Some(entry) = self.get("color") { style = style.foreground(entry.as_color()?); }
+        if let Some(entry) = self.get("background") { style = style.background(entry.as_color()?); }
+        if let Some(entry) = self.get("bold") { if entry.as_bool()? { style = style.bold(); } }
+        if let Some(entry) = self.get("dim") { if entry.as_bool()? { style = style.dim(); } }
+        if let Some(entry) = self.get("charset") { style = style.glyphs(entry.as_charset()?); }
+        if let Some(entry) = self.get("glyph") { style = style.glyph(entry.as_glyph()?); }
+
+        Ok(if style == Style::new() { None } else { Some(style) })
+    }
+
+    // shapes can always have the style keys
+    fn check_keys(&self, allowed: &[&str], context: &str) -> Result<(), SceneError> {
+        let is_allowed = |key: &str| allowed.contains(&key) || (self.name == "shape" && STYLE_KEYS.contains(&key));
+
+        match self.entries.iter().find(|entry| !is_allowed(&entry.key)) {
+            Some(entry) => Err(SceneError::new(entry.line, entry.column, format!("unknown key '{}' for {}", entry.key, context))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Entry {
+    fn error(&self, message: String) -> SceneError {
+        SceneError::new(self.line, self.value_column, message)
+    }
+
+    fn as_str(&self) -> Result<&str, SceneError> {
+        match self.value {
+            Value::Str(ref value) => Ok(value),
+            _ => Err(self.error(format!("expected a string for '{}'", self.key))),
+        }
+    }
+
+    fn as_number(&self) -> Result<u32, SceneError> {
+        match self.value {
+            Value::Integer(value) => to_number(value).ok_or_else(|| self.error(format!("'{}' must be a non-negative integer", self.key))),
+            _ => Err(self.error(format!("expected an integer for '{}'", self.key))),
+        }
+    }
+
+    fn as_angle(&self) -> Result<f64, SceneError> {
+        match self.value {
+            Value::Integer(value) => Ok(value as f64),
+            _ => Err(self.error(format!("expected an angle in degrees for '{}'", self.key))),
+        }
+    }
+
+    fn as_bool(&self) -> Result<bool, SceneError> {
+        match self.value {
+            Value::Boolean(value) => Ok(value),
+            _ => Err(self.error(format!("expected true or false for '{}'", self.key))),
+        }
+    }
+
+    fn as_y_axis(&self) -> Result<CoordinateSystem, SceneError> {
+        match self.as_str()? {
+            "up" => Ok(CoordinateSystem::Cartesian),
+            "down" => Ok(CoordinateSystem::Screen),
+            other => Err(self.error(format!("invalid y axis '{}', expected \"up\" or \"down\"", other))),
+        }
+    }
+
+    fn as_color(&self) -> Result<Color, SceneError> {
+        let color = match self.value {
+            Value::Str(ref value) => Color::parse(value),
+            Value::Integer(value) if (0..=255).contains(&value) => Some(Color::Indexed(value as u8)),
+            _ => None,
+        };
+
+        color.ok_or_else(|| self.error(format!("expected a color like \"red\", \"#ff8000\" or 208 for '{}'", self.key)))
+    }
+
+    fn as_glyph(&self) -> Result<char, SceneError> {
+        match self.value {
+            Value::Str(ref value) if value.chars().count() == 1 => Ok(value.chars().next().unwrap()),
+            _ => Err(self.error(format!("expected a single character for '{}'", self.key))),
+        }
+    }
+
+    fn as_charset(&self) -> Result<Charset, SceneError> {
+        let charset = match self.value {
+            Value::Str(ref value) => Charset::from_name(value),
+            _ => None,
+        };
+
+        charset.ok_or_else(|| self.error(format!("expected one of \"ascii\", \"unicode\", \"heavy\", \"double\" or \"rounded\" for '{}'", self.key)))
+    }
+
+    fn as_coords(&self) -> Result<Coords, SceneError> {
+        match self.value {
+            Value::Array(ref elements) if elements.len() == 2 => {
+                let mut coords = elements.iter().map(|&(value, column)| {
+                    to_coordinate(value).ok_or_else(|| SceneError::new(self.line, column, format!("coordinate in '{}' is out of range", self.key)))
+                });
+
+                let x = coords.next().unwrap()?;
+                let y = coords.next().unwrap()?;
+
+                Ok(Coords(x, y))
+            },
+            _ => Err(self.error(format!("expected coordinates like [x, y] for '{}'", self.key))),
+        }
+    }
+
+    fn as_coords_list(&self) -> Result<Vec<Coords>, SceneError> {
+        match self.value {
+            Value::Array(ref elements) if elements.len() % 2 == 0 => {
+                let coordinates = elements.iter()
+                    .map(|&(value, column)| {
+                        to_coordinate(value).ok_or_else(|| SceneError::new(self.line, column, format!("coordinate in '{}' is out of range", self.key)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(coordinates.chunks(2).map(|pair| Coords(pair[0], pair[1])).collect())
+            },
+            _ => Err(self.error(format!("expected coordinates like [x0, y0, x1, y1, ...] for '{}'", self.key))),
+        }
+    }
+}
+
+fn to_number(value: i64) -> Option<u32> {
+    if value >= 0 && value <= i64::from(u32::MAX) { Some(value as u32) } else { None }
+}
+
+fn to_coordinate(value: i64) -> Option<i32> {
+    if value >= i64::from(i32::MIN) && value <= i64::from(i32::MAX) { Some(value as i32) } else { None }
+}
+
+// -- parsing --
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, chr) in line.char_indices() {
+        match chr {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {},
+        }
+    }
+
+    line
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|chr| chr.is_ascii_alphanumeric() || chr == '_' || chr == '-')
+}
+
+fn skip_whitespace(chars: &[char], mut i: usize) -> usize {
+    while i < chars.len() && chars[i].is_whitespace() {
+        i += 1;
+    }
+
+    i
+}
+
+// columns are 1-based, `offset` is the column of chars[0] minus one
+fn parse_value(chars: &[char], line: usize, offset: usize) -> Result<Value, SceneError> {
+    let error = |i: usize, message: &str| SceneError::new(line, offset + i + 1, message.to_string());
+
+    let (value, end) = match chars.first() {
+        Some(&'"') => {
+            let mut value = String::new();
+            let mut i = 1;
+
+            loop {
+                match chars.get(i) {
+                    None => return Err(error(0, "unterminated string")),
+                    Some(&'"') => break,
+                    Some(&'\\') => {
+                        match chars.get(i + 1) {
+                            Some(&'"') => value.push('"'),
+                            Some(&'\\') => value.push('\\'),
+                            Some(&'n') => value.push('\n'),
+                            _ => return Err(error(i, "invalid escape sequence")),
+                        }
+                        i += 1;
+                    },
+                    Some(&chr) => value.push(chr),
+                }
+                i += 1;
+            }
+
+            (Value::Str(value), i + 1)
+        },
+        Some(&'[') => {
+            let mut elements = vec![];
+            let mut i = skip_whitespace(chars, 1);
+
+            if chars.get(i) != Some(&']') {
+                loop {
+                    let (element, end) = parse_integer(chars, i).ok_or_else(|| error(i, "expected an integer"))?;
+                    elements.push((element, offset + i + 1));
+
+                    i = skip_whitespace(chars, end);
+
+                    match chars.get(i) {
+                        Some(&',') => i = skip_whitespace(chars, i + 1),
+                        Some(&']') => break,
+                        _ => return Err(error(i, "expected ',' or ']'")),
+                    }
+                }
+            }
+
+            (Value::Array(elements), i + 1)
+        },
+        Some(_) if chars.starts_with(&['t', 'r', 'u', 'e']) => (Value::Boolean(true), 4),
+        Some(_) if chars.starts_with(&['f', 'a', 'l', 's', 'e']) => (Value::Boolean(false), 5),
+        Some(_) => {
+            let (value, end) = parse_integer(chars, 0).ok_or_else(|| error(0, "expected a string, an integer, a boolean or an array"))?;
+            (Value::Integer(value), end)
+        },
+        None => return Err(error(0, "missing value")),
+    };
+
+    let end = skip_whitespace(chars, end);
+
+    if end < chars.len() {
+        return Err(error(end, "unexpected characters after value"));
+    }
+
+    Ok(value)
+}
+
+fn parse_integer(chars: &[char], start: usize) -> Option<(i64, usize)> {
+    let mut end = start;
+
+    if chars.get(end) == Some(&'-') || chars.get(end) == Some(&'+') {
+        end += 1;
+    }
+
+    while end < chars.len() && chars[end].is_ascii_digit() {
+        end += 1;
+    }
+
+    chars[start..end].iter().collect::<String>().parse::<i64>().ok().map(|value| (value, end))
+}
+
+fn parse_tables(source: &str) -> Result<Vec<Table>, SceneError> {
+    let mut tables: Vec<Table> = vec![];
+
+    for (i, raw_line) in source.lines().enumerate() {
+        let line = i + 1;
+        let content = strip_comment(raw_line);
+        let trimmed = content.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let column = content.chars().take_while(|chr| chr.is_whitespace()).count() + 1;
+
+        if trimmed.starts_with('[') {
+            let name = if trimmed.starts_with("[[") && trimmed.ends_with("]]") && trimmed.len() >= 4 {
+                &trimmed[2..trimmed.len() - 2]
+            } else if !trimmed.starts_with("[[") && trimmed.ends_with(']') {
+                &trimmed[1..trimmed.len() - 1]
+            } else {
+                return Err(SceneError::new(line, column, format!("malformed table header '{}'", trimmed)));
+            };
+
+            match (name.trim(), trimmed.starts_with("[[")) {
+                ("shape", true) | ("canvas", false) => {},
+                _ => return Err(SceneError::new(line, column, format!("unknown table '{}', expected [canvas] or [[shape]]", trimmed))),
+            }
+
+            if name.trim() == "canvas" && tables.iter().any(|table| table.name == "canvas") {
+                return Err(SceneError::new(line, column, "duplicate table [canvas]".to_string()));
+            }
+
+            tables.push(Table { name: name.trim().to_string(), line, column, entries: vec![] });
+            continue;
+        }
+
+        let separator = match trimmed.find('=') {
+            Some(separator) => separator,
+            None => return Err(SceneError::new(line, column, format!("expected 'key = value', found '{}'", trimmed))),
+        };
+
+        let key = trimmed[..separator].trim();
+
+        if !is_bare_key(key) {
+            return Err(SceneError::new(line, column, format!("invalid key '{}'", key)));
+        }
+
+        let value_part = &trimmed[separator + 1..];
+        let value_trimmed = value_part.trim_start();
+        let value_column = column - 1
+            + trimmed[..separator + 1].chars().count()
+            + (value_part.chars().count() - value_trimmed.chars().count());
+        let value = parse_value(&value_trimmed.chars().collect::<Vec<_>>(), line, value_column)?;
+
+        let table = match tables.last_mut() {
+            Some(table) => table,
+            None => return Err(SceneError::new(line, column, "expected a table header like [[shape]] before the first key".to_string())),
+        };
+
+        if table.get(key).is_some() {
+            return Err(SceneError::new(line, column, format!("duplicate key '{}'", key)));
+        }
+
+        table.entries.push(Entry {
+            key: key.to_string(),
+            value,
+            line,
+            column,
+            value_column: value_column + 1,
+        });
+    }
+
+    Ok(tables)
+}
+
+fn build_shape(table: &Table, aspect_ratio: f64) -> Result<Box<dyn Plottable>, SceneError> {
+    let kind = table.require("type", "shape")?;
+
+    match kind.as_str()? {
+        "line" => {
+            table.check_keys(&["type", "from", "to"], "line")?;
+
+            let start = table.require("from", "line")?.as_coords()?;
+            let end = table.require("to", "line")?.as_coords()?;
+
+            Ok(Box::new(Line::new(start, end).map_err(|e| table.error(e))?))
+        },
+        "circle" => {
+            table.check_keys(&["type", "center", "radius", "round", "filled"], "circle")?;
+
+            let center = table.require("center", "circle")?.as_coords()?;
+            let radius = table.require("radius", "circle")?.as_number()?;
+            let round = match table.get("round") {
+                Some(entry) => entry.as_bool()?,
+                None => false,
+            };
+
+            let circle = if round { Circle::with_aspect_ratio(center, radius, aspect_ratio) } else { Circle::new(center, radius) };
+            let circle = circle.map_err(|e| table.error(e))?;
+            Ok(Box::new(if table.is_filled()? { circle.filled() } else { circle }))
+        },
+        "ellipse" => {
+            table.check_keys(&["type", "center", "radius_x", "radius_y", "filled"], "ellipse")?;
+
+            let center = table.require("center", "ellipse")?.as_coords()?;
+            let radius_x = table.require("radius_x", "ellipse")?.as_number()?;
+            let radius_y = table.require("radius_y", "ellipse")?.as_number()?;
+
+            let ellipse = Ellipse::new(center, radius_x, radius_y).map_err(|e| table.error(e))?;
+            Ok(Box::new(if table.is_filled()? { ellipse.filled() } else { ellipse }))
+        },
+        "arc" => {
+            table.check_keys(&["type", "center", "radius", "start", "end"], "arc")?;
+
+            let center = table.require("center", "arc")?.as_coords()?;
+            let radius = table.require("radius", "arc")?.as_number()?;
+            let start_angle = table.require("start", "arc")?.as_angle()?;
+            let end_angle = table.require("end", "arc")?.as_angle()?;
+
+            Ok(Box::new(Arc::new(center, radius, start_angle, end_angle).map_err(|e| table.error(e))?))
+        },
+        "rectangle" => {
+            table.check_keys(&["type", "from", "to", "rounded", "filled"], "rectangle")?;
+
+            let corner = table.require("from", "rectangle")?.as_coords()?;
+            let opposite_corner = table.require("to", "rectangle")?.as_coords()?;
+            let mut rectangle = Rectangle::new(corner, opposite_corner).map_err(|e| table.error(e))?;
+
+            if let Some(entry) = table.get("rounded") {
+                if entry.as_bool()? { rectangle = rectangle.rounded(); }
+            }
+
+            if table.is_filled()? {
+                rectangle = rectangle.filled();
+            }
+
+            Ok(Box::new(rectangle))
+        },
+        "polyline" | "polygon" => {
+            let context = kind.as_str()?;
+
+            if context == "polyline" {
+                table.check_keys(&["type", "points"], context)?;
+            } else {
+                table.check_keys(&["type", "points", "filled"], context)?;
+            }
+
+            let vertices = table.require("points", context)?.as_coords_list()?;
+
+            if context == "polyline" {
+                Ok(Box::new(Polyline::new(vertices).map_err(|e| table.error(e))?))
+            } else {
+                let polygon = Polygon::new(vertices).map_err(|e| table.error(e))?;
+                Ok(Box::new(if table.is_filled()? { polygon.filled() } else { polygon }))
+            }
+        },
+        "bezier" => {
+            table.check_keys(&["type", "points"], "bezier")?;
+
+            let entry = table.require("points", "bezier")?;
+            let points = entry.as_coords_list()?;
+
+            match points.len() {
+                3 => Ok(Box::new(QuadraticBezier::new(points[0], points[1], points[2]).map_err(|e| table.error(e))?)),
+                4 => Ok(Box::new(CubicBezier::new(points[0], points[1], points[2], points[3]).map_err(|e| table.error(e))?)),
+                _ => Err(entry.error(format!("a bezier curve needs 3 or 4 points, got {}", points.len()))),
+            }
+        },
+        other => Err(kind.error(format!("unknown shape type '{}', expected \"line\", \"circle\", \"ellipse\", \"arc\", \"rectangle\", \"polyline\", \"polygon\" or \"bezier\"", other))),
+    }
+}
+
+// -- functions --
+// round circles are stretched to look round when rendered with the default RenderOptions
+pub fn parse_scene(source: &str) -> Result<Scene, SceneError> {
+    parse_scene_with(source, RenderOptions::new().get_aspect_ratio())
+}
+
+// round circles are stretched by the aspect ratio of the cells, e.g. RenderOptions::get_aspect_ratio()
+pub fn parse_scene_with(source: &str, aspect_ratio: f64) -> Result<Scene, SceneError> {
+    let tables = parse_tables(source)?;
+
+    let mut shapes: Vec<Box<dyn Plottable>> = vec![];
+    let mut width = None;
+    let mut height = None;
+    let mut coordinate_system = CoordinateSystem::default();
+
+    for table in tables.iter() {
+        if table.name == "canvas" {
+            table.check_keys(&["width", "height", "y_axis"], "canvas")?;
+
+            if let Some(entry) = table.get("width") { width = Some(entry.as_number()?); }
+            if let Some(entry) = table.get("height") { height = Some(entry.as_number()?); }
+            if let Some(entry) = table.get("y_axis") { coordinate_system = entry.as_y_axis()?; }
+        } else {
+            let shape = build_shape(table, aspect_ratio)?;
+
+            match table.get_style()? {
+                Some(style) => shapes.push(Box::new(Styled::new(shape, style))),
+                None => shapes.push(shape),
+            }
+        }
+    }
+
+    if shapes.is_empty() {
+        return Err(SceneError::new(1, 1, "scene does not contain any [[shape]]".to_string()));
+    }
+
+    let mut contained_coords = vec![];
+
+    for shape in shapes.iter() {
+        contained_coords.extend(shape.get_coords());
+    }
+
+    let Dimensions(fit_width, fit_height) = Dimensions::from_coords(&contained_coords);
+    let dimensions = Dimensions(width.unwrap_or(fit_width), height.unwrap_or(fit_height));
+
+    Ok(Scene(CombinedObject(dimensions, shapes), coordinate_system))
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, plot_in, render};
+    use coords::{PlottedCoords, Representation};
+
+    fn error_at(source: &str) -> (usize, usize, String) {
+        let error = parse_scene(source).err().unwrap();
+        (error.line, error.column, error.message)
+    }
+
+    #[test]
+    fn test_parse_scene() {
+        let scene = parse_scene("
+# a line and a circle
+[[shape]]
+type = \"line\"
+from = [0, 0]
+to = [2, 2] # diagonal
+
+[[shape]]
+type = \"circle\"
+center = [ 1, 1 ]
+radius = 1
+").unwrap();
+
+        assert_eq!(Dimensions(3, 3), scene.get_dimensions());
+        assert_eq!(2, (scene.0).1.len());
+        assert_eq!(CoordinateSystem::Cartesian, scene.get_coordinate_system());
+
+        let canvas = plot(Box::new(scene)).unwrap();
+
+        assert_eq!(canvas.1[1], PlottedCoords(1, 2, Representation::Circle, None));
+        assert_eq!(canvas.1[2], PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, None));
+    }
+
+    #[test]
+    fn test_parse_scene_negative_coords() {
+        let scene = parse_scene("[[shape]]\ntype = \"circle\"\ncenter = [0, -1]\nradius = 1\n").unwrap();
+
+        assert_eq!(Coords(-1, -2), scene.get_origin());
+        assert_eq!(Dimensions(3, 3), scene.get_dimensions());
+    }
+
+    #[test]
+    fn test_parse_scene_round_circle() {
+        let source = "[[shape]]\ntype = \"circle\"\ncenter = [0, 0]\nradius = 2\nround = true\n";
+
+        assert_eq!(Dimensions(5, 5), parse_scene(source).unwrap().get_dimensions());
+        assert_eq!(Dimensions(9, 5), parse_scene_with(source, 2.0).unwrap().get_dimensions());
+        assert_eq!("invalid aspect ratio 0, it has to be a positive number", parse_scene_with(source, 0.0).err().unwrap().message);
+    }
+
+    #[test]
+    fn test_parse_scene_canvas_size() {
+        let scene = parse_scene("[canvas]\nwidth = 10\n\n[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\n").unwrap();
+
+        assert_eq!(Dimensions(10, 1), scene.get_dimensions());
+    }
+
+    #[test]
+    fn test_parse_scene_y_axis() {
+        let source = "[canvas]\ny_axis = \"down\"\n\n[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 2]\n";
+        let scene = parse_scene(source).unwrap();
+        let coordinate_system = scene.get_coordinate_system();
+
+        assert_eq!(CoordinateSystem::Screen, coordinate_system);
+        let canvas = plot_in(Box::new(scene), coordinate_system).unwrap();
+        assert_eq!("\\    \n  \\  \n    \\\n", render(&canvas, &Charset::ascii()));
+
+        let error = parse_scene("[canvas]\ny_axis = \"left\"\n").err().unwrap();
+        assert_eq!((2, 10), (error.line, error.column));
+        assert_eq!("invalid y axis 'left', expected \"up\" or \"down\"", error.message);
+    }
+
+    #[test]
+    fn test_parse_scene_rectangle() {
+        let scene = parse_scene("[[shape]]\ntype = \"rectangle\"\nfrom = [0, 0]\nto = [2, 2]\nrounded = true\nfilled = false\n").unwrap();
+        let canvas = plot(Box::new(scene)).unwrap();
+
+        assert_eq!(canvas.1[0], PlottedCoords(0, 2, Representation::RoundedTopLeftCorner, None));
+        assert_eq!(canvas.1[4], PlottedCoords(1, 1, Representation::Canvas, None));
+
+        assert_eq!((5, 11, "expected true or false for 'rounded'".to_string()), error_at("[[shape]]\ntype = \"rectangle\"\nfrom = [0, 0]\nto = [2, 2]\nrounded = 1"));
+    }
+
+    #[test]
+    fn test_parse_scene_ellipse_and_arc() {
+        let scene = parse_scene("[[shape]]\ntype = \"ellipse\"\ncenter = [4, 2]\nradius_x = 4\nradius_y = 2\n\n[[shape]]\ntype = \"arc\"\ncenter = [0, 0]\nradius = 2\nstart = -90\nend = 90\n").unwrap();
+
+        assert_eq!(Coords(0, -2), scene.get_origin());
+        assert_eq!(Dimensions(9, 7), scene.get_dimensions());
+    }
+
+    #[test]
+    fn test_parse_scene_polygon() {
+        let scene = parse_scene("[[shape]]\ntype = \"polygon\"\npoints = [0, 0, 2, 0, 0, 2]\n").unwrap();
+
+        assert_eq!(Dimensions(3, 3), scene.get_dimensions());
+
+        let scene = parse_scene("[[shape]]\ntype = \"polygon\"\npoints = [0, 0, 4, 0, 0, 4]\nfilled = true\n").unwrap();
+        assert_eq!(Some(Representation::Fill), scene.get_representation_at(Coords(1, 1)));
+
+        assert_eq!((4, 1, "unknown key 'filled' for polyline".to_string()), error_at("[[shape]]\ntype = \"polyline\"\npoints = [0, 0, 2, 0]\nfilled = true"));
+        assert_eq!((3, 10, "expected coordinates like [x0, y0, x1, y1, ...] for 'points'".to_string()), error_at("[[shape]]\ntype = \"polyline\"\npoints = [0, 0, 2]"));
+    }
+
+    #[test]
+    fn test_parse_scene_style() {
+        let scene = parse_scene("[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\ncolor = \"red\"\nbackground = 17\nbold = true\n").unwrap();
+        let canvas = plot(Box::new(scene)).unwrap();
+
+        assert_eq!(Some(Style::new().foreground(Color::Red).background(Color::Indexed(17)).bold()), canvas.1[0].3);
+        assert_eq!((5, 9, "expected a color like \"red\", \"#ff8000\" or 208 for 'color'".to_string()), error_at("[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\ncolor = \"orange\""));
+        assert_eq!((2, 1, "unknown key 'color' for canvas".to_string()), error_at("[canvas]\ncolor = \"red\"\n[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]"));
+    }
+
+    #[test]
+    fn test_parse_scene_glyphs() {
+        let scene = parse_scene("[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\nglyph = \"=\"\n\n[[shape]]\ntype = \"line\"\nfrom = [0, 1]\nto = [2, 1]\ncharset = \"double\"\n").unwrap();
+        let canvas = plot(Box::new(scene)).unwrap();
+
+        assert_eq!("═ ═ ═\n= = =\n", render(&canvas, &Charset::ascii()));
+        assert_eq!((5, 9, "expected a single character for 'glyph'".to_string()), error_at("[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\nglyph = \"==\""));
+        assert_eq!((5, 11, "expected one of \"ascii\", \"unicode\", \"heavy\", \"double\" or \"rounded\" for 'charset'".to_string()), error_at("[[shape]]\ntype = \"line\"\nfrom = [0, 0]\nto = [2, 0]\ncharset = \"thin\""));
+    }
+
+    #[test]
+    fn test_parse_scene_bezier() {
+        let scene = parse_scene("[[shape]]\ntype = \"bezier\"\npoints = [0, 0, 4, 0, 0, 4, 4, 4]\n").unwrap();
+
+        assert_eq!(Dimensions(5, 5), scene.get_dimensions());
+        assert_eq!((3, 10, "a bezier curve needs 3 or 4 points, got 2".to_string()), error_at("[[shape]]\ntype = \"bezier\"\npoints = [0, 0, 4, 4]"));
+    }
+
+    #[test]
+    fn test_parse_scene_reports_position_of_errors() {
+        assert_eq!((3, 9, "expected ',' or ']'".to_string()), error_at("[[shape]]\ntype = \"line\"\nto = [0 0]"));
+        assert_eq!((2, 8, "unknown shape type 'square', expected \"line\", \"circle\", \"ellipse\", \"arc\", \"rectangle\", \"polyline\", \"polygon\" or \"bezier\"".to_string()), error_at("[[shape]]\ntype = \"square\""));
+        assert_eq!((1, 1, "missing key 'radius' for circle".to_string()), error_at("[[shape]]\ntype = \"circle\"\ncenter = [1, 1]"));
+        assert_eq!((4, 3, "unknown key 'size' for circle".to_string()), error_at("[[shape]]\ntype = \"circle\"\ncenter = [1, 1]\n  size = 3\nradius = 1"));
+        assert_eq!((3, 12, "coordinate in 'from' is out of range".to_string()), error_at("[[shape]]\ntype = \"line\"\nfrom = [0, -3000000000]\nto = [1, 1]"));
+        assert_eq!((2, 8, "unterminated string".to_string()), error_at("[[shape]]\ntype = \"line"));
+        assert_eq!((1, 1, "expected a table header like [[shape]] before the first key".to_string()), error_at("type = \"line\""));
+        assert_eq!((2, 1, "unknown table '[[square]]', expected [canvas] or [[shape]]".to_string()), error_at("\n[[square]]"));
+        assert_eq!((2, 1, "invalid radius 0, the circle has to be larger than a point and within -1073741824 and 1073741824".to_string()), error_at("\n[[shape]]\ntype = \"circle\"\ncenter = [1, 1]\nradius = 0"));
+        assert_eq!((1, 1, "scene does not contain any [[shape]]".to_string()), error_at("[canvas]\nwidth = 3"));
+    }
+
+    #[test]
+    fn test_scene_error_display() {
+        let error = SceneError::new(3, 7, "missing value".to_string());
+
+        assert_eq!("line 3, column 7: missing value", error.to_string());
+    }
+}
@@ -0,0 +1,291 @@
+use std::env;
+use std::io::IsTerminal;
+use std::sync::Arc;
+
+use canvas::Charset;
+use coords::{Coords, Dimensions, Path, Representation};
+use plottable::Plottable;
+use raster::Raster;
+
+// switches the terminal back to the default style
+pub const RESET: &str = "\x1b[0m";
+
+// -- structs --
+// the 16 colors every terminal supports, the 256 colors of the xterm palette or a 24-bit color
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+const NAMED_COLORS: [(&str, Color); 16] = [
+    ("black", Color::Black),
+    ("red", Color::Red),
+    ("green", Color::Green),
+    ("yellow", Color::Yellow),
+    ("blue", Color::Blue),
+    ("magenta", Color::Magenta),
+    ("cyan", Color::Cyan),
+    ("white", Color::White),
+    ("bright-black", Color::BrightBlack),
+    ("bright-red", Color::BrightRed),
+    ("bright-green", Color::BrightGreen),
+    ("bright-yellow", Color::BrightYellow),
+    ("bright-blue", Color::BrightBlue),
+    ("bright-magenta", Color::BrightMagenta),
+    ("bright-cyan", Color::BrightCyan),
+    ("bright-white", Color::BrightWhite),
+];
+
+impl Color {
+    // a name like 'red' or 'bright-red', an index into the 256 color palette like '208' or '#rrggbb'
+    pub fn parse(value: &str) -> Option<Color> {
+        if let Some(&(_, color)) = NAMED_COLORS.iter().find(|&&(name, _)| name == value) {
+            return Some(color);
+        }
+
+        if let Some(hex) = value.strip_prefix('#') {
+            if hex.len() != 6 || !hex.chars().all(|chr| chr.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+            return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+        }
+
+        value.parse::<u8>().ok().map(Color::Indexed)
+    }
+
+    // the parameters of the SGR escape sequence, `base` is 30 for the foreground and 40 for the background
+    fn get_parameters(self, base: u8) -> String {
+        let extended = base + 8;
+
+        match self {
+            Color::Indexed(index) => format!("{};5;{}", extended, index),
+            Color::Rgb(r, g, b) => format!("{};2;{};{};{}", extended, r, g, b),
+            named => {
+                let index = NAMED_COLORS.iter().position(|&(_, color)| color == named).unwrap() as u8;
+
+                // the bright colors are 60 above the normal ones
+                if index < 8 { format!("{}", base + index) } else { format!("{}", base + 60 + index - 8) }
+            },
+        }
+    }
+}
+
+// the glyphs an object is drawn with instead of those of the charset passed to render()
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum Glyphs {
+    Single(char),
+    Charset(Charset),
+}
+
+// every cell drawn in a style refers to the same style data, which keeps the cells of a canvas small
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct Style(Arc<StyleData>);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+struct StyleData {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+    dim: bool,
+    glyphs: Option<Glyphs>,
+}
+
+impl Style {
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    pub fn foreground(mut self, color: Color) -> Style {
+        Arc::make_mut(&mut self.0).foreground = Some(color);
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Style {
+        Arc::make_mut(&mut self.0).background = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> Style {
+        Arc::make_mut(&mut self.0).bold = true;
+        self
+    }
+
+    pub fn dim(mut self) -> Style {
+        Arc::make_mut(&mut self.0).dim = true;
+        self
+    }
+
+    // every cell of the object is drawn with this glyph
+    pub fn glyph(mut self, glyph: char) -> Style {
+        Arc::make_mut(&mut self.0).glyphs = Some(Glyphs::Single(glyph));
+        self
+    }
+
+    // the object is drawn with the glyphs of this charset, whichever charset the canvas is rendered with
+    pub fn glyphs(mut self, charset: Charset) -> Style {
+        Arc::make_mut(&mut self.0).glyphs = Some(Glyphs::Charset(charset));
+        self
+    }
+
+    // the glyph overriding the one of the charset, empty cells are never overridden
+    pub fn get_glyph(&self, representation: &Representation) -> Option<char> {
+        if *representation == Representation::Canvas {
+            return None;
+        }
+
+        match self.0.glyphs {
+            Some(Glyphs::Single(glyph)) => Some(glyph),
+            Some(Glyphs::Charset(ref charset)) => Some(charset.get_glyph(representation)),
+            None => None,
+        }
+    }
+
+    // the escape sequence switching the terminal to this style, empty for the default style
+    pub fn get_escape_sequence(&self) -> String {
+        let mut parameters = vec![];
+
+        if self.0.bold { parameters.push("1".to_string()); }
+        if self.0.dim { parameters.push("2".to_string()); }
+
+        if let Some(color) = self.0.foreground { parameters.push(color.get_parameters(30)); }
+        if let Some(color) = self.0.background { parameters.push(color.get_parameters(40)); }
+
+        if parameters.is_empty() { String::new() } else { format!("\x1b[{}m", parameters.join(";")) }
+    }
+}
+
+// an object drawn in a style, a style given to a contained object wins over this one
+#[derive(Debug)]
+pub struct Styled<P: Plottable>(P, Style);
+
+impl<P: Plottable> Styled<P> {
+    pub fn new(plottable: P, style: Style) -> Styled<P> {
+        Styled(plottable, style)
+    }
+
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P: Plottable> Plottable for Styled<P> {
+    fn get_dimensions(&self) -> Dimensions {
+        self.0.get_dimensions()
+    }
+
+    fn get_origin(&self) -> Coords {
+        self.0.get_origin()
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        self.0.get_coords()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        self.0.get_representation_at(coords)
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        self.0.get_paths()
+    }
+
+    // the style of a contained object wins, like when rasterizing
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        self.0.get_styled_paths().into_iter()
+            .map(|(path, style)| (path, style.or_else(|| Some(self.1.clone()))))
+            .collect()
+    }
+
+    fn get_style(&self) -> Option<Style> {
+        Some(self.1.clone())
+    }
+
+    fn rasterize(&self, raster: &mut Raster) {
+        let previous = raster.set_style(Some(self.1.clone()));
+        self.0.rasterize(raster);
+        raster.set_style(previous);
+    }
+}
+
+// -- functions --
+// colors are only written to terminals and never if the NO_COLOR environment variable is set, see no-color.org
+pub fn supports_color<T: IsTerminal>(stream: &T) -> bool {
+    env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && stream.is_terminal()
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::plot;
+    use coords::PlottedCoords;
+    use fixtures::*;
+    use plottable::combine;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(Some(Color::Red), Color::parse("red"));
+        assert_eq!(Some(Color::BrightCyan), Color::parse("bright-cyan"));
+        assert_eq!(Some(Color::Indexed(208)), Color::parse("208"));
+        assert_eq!(Some(Color::Rgb(255, 128, 0)), Color::parse("#ff8000"));
+        assert_eq!(None, Color::parse("#ff80"));
+        assert_eq!(None, Color::parse("256"));
+        assert_eq!(None, Color::parse("orange"));
+    }
+
+    #[test]
+    fn test_escape_sequence() {
+        assert_eq!("", Style::new().get_escape_sequence());
+        assert_eq!("\x1b[31m", Style::new().foreground(Color::Red).get_escape_sequence());
+        assert_eq!("\x1b[1;92;44m", Style::new().bold().foreground(Color::BrightGreen).background(Color::Blue).get_escape_sequence());
+        assert_eq!("\x1b[2;38;5;208;48;2;1;2;3m", Style::new().dim().foreground(Color::Indexed(208)).background(Color::Rgb(1, 2, 3)).get_escape_sequence());
+    }
+
+    #[test]
+    fn test_styled_objects_keep_their_style() {
+        let red = Style::new().foreground(Color::Red);
+        let blue = Style::new().foreground(Color::Blue);
+
+        let line = Styled::new(fixtures_horizontal_line(), red.clone());
+        let diagonal = fixtures_diagonal_line_ltr();
+        let combined = Styled::new(combine(Box::new(line), Box::new(diagonal)).unwrap(), blue.clone());
+
+        let canvas = plot(Box::new(combined)).unwrap();
+
+        // the inner style wins, the first object wins at (0, 0) with its style
+        assert!(canvas.1.contains(&PlottedCoords(0, 0, Representation::HorizontalLine, Some(red))));
+        assert!(canvas.1.contains(&PlottedCoords(1, 1, Representation::DiagonalLineLeftToRight, Some(blue.clone()))));
+        assert!(canvas.1.contains(&PlottedCoords(2, 2, Representation::DiagonalLineLeftToRight, Some(blue))));
+        assert!(canvas.1.contains(&PlottedCoords(0, 2, Representation::Canvas, None)));
+    }
+
+    #[test]
+    fn test_get_glyph() {
+        let star = Style::new().glyph('*');
+        let heavy = Style::new().glyphs(Charset::heavy());
+
+        assert_eq!(Some('*'), star.get_glyph(&Representation::Circle));
+        assert_eq!(None, star.get_glyph(&Representation::Canvas));
+        assert_eq!(Some('━'), heavy.get_glyph(&Representation::HorizontalLine));
+        assert_eq!(None, Style::new().bold().get_glyph(&Representation::HorizontalLine));
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use coords::{Coords, Dimensions, Path, Representation};
+use error::Error;
+use fill::Filling;
+use line::Line;
+use plottable::Plottable;
+use raster::Raster;
+use style::Style;
+
+// -- structs --
+// the cells of an object after mapping its paths to new positions
+struct MappedCells {
+    dimensions: Dimensions,
+    coords: Vec<Coords>,
+    representations: HashMap<Coords, Representation>,
+    styles: HashMap<Coords, Style>,
+    paths: Vec<(Path, Option<Style>)>,
+}
+
+impl MappedCells {
+    // maps every path of the object and rasterizes it again, line and corner representations are mapped by direction
+    // and filled areas are filled again
+    fn new<P, F, G>(plottable: &P, map_point: F, map_direction: G) -> Result<MappedCells, Error>
+        where P: Plottable + ?Sized, F: Fn(f64, f64) -> (f64, f64), G: Fn(f64, f64) -> (f64, f64)
+    {
+        let mut coords = vec![];
+        let mut representations = HashMap::new();
+        let mut styles = HashMap::new();
+        let mut paths = vec![];
+
+        let map_representation = |representation: Representation| {
+            if let Some((dx, dy)) = representation.get_direction() {
+                let (dx, dy) = map_direction(dx, dy);
+                Representation::from_direction(dx, dy)
+            } else if let Some((dx, dy)) = representation.get_corner_direction() {
+                let (dx, dy) = map_direction(dx, dy);
+                Representation::corner_from_direction(dx, dy, representation.is_rounded_corner())
+            } else {
+                representation
+            }
+        };
+
+        for (Path(representation, path), style) in plottable.get_styled_paths() {
+            let representation = map_representation(representation);
+            let mapped_path = path.iter()
+                .map(|&Coords(x, y)| {
+                    let (x, y) = map_point(f64::from(x), f64::from(y));
+                    Coords::from_point(x, y)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // a filled area is filled again inside its mapped outline, mapping the filled cells would leave gaps
+            // wherever the area grows
+            let cells = if representation == Representation::Fill {
+                Filling::new(&mapped_path, &[]).get_coords().iter().map(|&cell| (cell, Representation::Fill)).collect()
+            } else {
+                trace(&mapped_path, &representation)?
+            };
+
+            // cells drawn first win, e.g. the outline over its fill
+            for (cell, cell_representation) in cells {
+                if let Entry::Vacant(entry) = representations.entry(cell) {
+                    entry.insert(cell_representation);
+                    coords.push(cell);
+
+                    if let Some(ref style) = style {
+                        styles.insert(cell, style.clone());
+                    }
+                }
+            }
+
+            paths.push((Path(representation, mapped_path), style));
+        }
+
+        let dimensions = Dimensions::from_coords(&coords);
+        Ok(MappedCells { dimensions, coords, representations, styles, paths })
+    }
+}
+
+// affine transform of the plane, mapping (x, y) to (a * x + b * y + c, d * x + e * y + f)
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Transform([f64; 6]);
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform([1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
+    }
+
+    pub fn translate(dx: f64, dy: f64) -> Transform {
+        Transform([1.0, 0.0, dx, 0.0, 1.0, dy])
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Transform {
+        Transform([sx, 0.0, 0.0, 0.0, sy, 0.0])
+    }
+
+    // mirrors left to right, use about() to mirror at any other vertical axis than x = 0
+    pub fn mirror_horizontally() -> Transform {
+        Transform::scale(-1.0, 1.0)
+    }
+
+    // mirrors top to bottom, use about() to mirror at any other horizontal axis than y = 0
+    pub fn mirror_vertically() -> Transform {
+        Transform::scale(1.0, -1.0)
+    }
+
+    // moves x by kx for every step in y and y by ky for every step in x
+    pub fn shear(kx: f64, ky: f64) -> Transform {
+        Transform([1.0, kx, 0.0, ky, 1.0, 0.0])
+    }
+
+    // rotates counter-clockwise by the angle in degrees
+    pub fn rotation(degrees: f64) -> Transform {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Transform([cos, -sin, 0.0, sin, cos, 0.0])
+    }
+
+    // applies this transform first and the next one to the result
+    pub fn then(self, next: Transform) -> Transform {
+        let Transform([a0, b0, c0, d0, e0, f0]) = self;
+        let Transform([a1, b1, c1, d1, e1, f1]) = next;
+
+        Transform([
+            a1 * a0 + b1 * d0, a1 * b0 + b1 * e0, a1 * c0 + b1 * f0 + c1,
+            d1 * a0 + e1 * d0, d1 * b0 + e1 * e0, d1 * c0 + e1 * f0 + f1,
+        ])
+    }
+
+    // the same transform with the pivot as its center instead of (0, 0), e.g. to scale or rotate around a pivot
+    pub fn about(self, pivot: Coords) -> Transform {
+        let (x, y) = (f64::from(pivot.0), f64::from(pivot.1));
+        Transform::translate(-x, -y).then(self).then(Transform::translate(x, y))
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        let Transform([a, b, c, d, e, f]) = *self;
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+
+    // maps a direction, which unlike a point isn't translated
+    pub fn apply_to_direction(&self, dx: f64, dy: f64) -> (f64, f64) {
+        let Transform([a, b, _, d, e, _]) = *self;
+        (a * dx + b * dy, d * dx + e * dy)
+    }
+}
+
+// an object moved by a transform, rasterized again from the paths of the object
+pub struct Transformed<P: Plottable>(P, MappedCells);
+
+pub type Rotated<P> = Transformed<P>;
+
+impl<P: Plottable> Transformed<P> {
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P: Plottable> Plottable for Transformed<P> {
+    fn get_dimensions(&self) -> Dimensions {
+        (self.1).dimensions
+    }
+
+    fn get_origin(&self) -> Coords {
+        Coords::origin_from_coords(&(self.1).coords)
+    }
+
+    fn get_coords(&self) -> Vec<Coords> {
+        (self.1).coords.clone()
+    }
+
+    fn get_representation_at(&self, coords: Coords) -> Option<Representation> {
+        (self.1).representations.get(&coords).cloned()
+    }
+
+    fn get_paths(&self) -> Vec<Path> {
+        (self.1).paths.iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    fn get_styled_paths(&self) -> Vec<(Path, Option<Style>)> {
+        (self.1).paths.clone()
+    }
+
+    fn get_style(&self) -> Option<Style> {
+        self.0.get_style()
+    }
+
+    // every cell is drawn in the style of the path it was mapped from, cells without one in the style of the raster
+    fn rasterize(&self, raster: &mut Raster) {
+        for coords in (self.1).coords.iter() {
+            let representation = (self.1).representations[coords].clone();
+
+            match (self.1).styles.get(coords) {
+                Some(style) => {
+                    let previous = raster.set_style(Some(style.clone()));
+                    raster.set(*coords, representation);
+                    raster.set_style(previous);
+                },
+                None => raster.set(*coords, representation),
+            }
+        }
+    }
+}
+
+// -- functions --
+// the cells of a path, a path of a single coords is just that cell, all others are connected by lines. lines keep
+// the glyph of every cell, anything else is drawn with the representation of the path
+fn trace(path: &[Coords], representation: &Representation) -> Result<Vec<(Coords, Representation)>, Error> {
+    if path.len() == 1 {
+        return Ok(vec![(path[0], representation.clone())]);
+    }
+
+    let mut cells = vec![];
+
+    for pair in path.windows(2) {
+        let line = Line::new(pair[0], pair[1])?;
+
+        cells.extend(line.get_cells().map(|(cell, line_representation)| match representation.get_direction() {
+            Some(_) => (*cell, line_representation.clone()),
+            None => (*cell, representation.clone()),
+        }));
+    }
+
+    Ok(cells)
+}
+
+pub fn transform<P: Plottable>(plottable: P, transform: Transform) -> Result<Transformed<P>, Error> {
+    if !(transform.0).iter().all(|value| value.is_finite()) {
+        return Err(Error::InvalidTransform("all factors have to be finite numbers"));
+    }
+
+    let cells = MappedCells::new(&plottable, |x, y| transform.apply(x, y), |dx, dy| transform.apply_to_direction(dx, dy))?;
+    Ok(Transformed(plottable, cells))
+}
+
+// rotates counter-clockwise by the angle in degrees
+pub fn rotate<P: Plottable>(plottable: P, degrees: f64, pivot: Coords) -> Result<Rotated<P>, Error> {
+    if !degrees.is_finite() {
+        return Err(Error::UnsupportedRotation("the angle has to be a finite number"));
+    }
+
+    transform(plottable, Transform::rotation(degrees).about(pivot))
+}
+
+// -- tests --
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::{plot, render, Charset};
+    use circle::Circle;
+    use coords::PlottedCoords;
+    use fixtures::*;
+    use plottable::combine;
+    use rectangle::Rectangle;
+    use style::{Color, Styled};
+
+    #[test]
+    fn test_rotate_line_by_90_degrees() {
+        let line = Line::new(Coords(0, 0), Coords(3, 0)).unwrap();
+        let rotated = rotate(line, 90.0, Coords(0, 0)).unwrap();
+
+        assert_eq!(vec![Coords(0, 0), Coords(0, 1), Coords(0, 2), Coords(0, 3)], rotated.get_coords());
+        assert_eq!(Some(Representation::VerticalLine), rotated.get_representation_at(Coords(0, 2)));
+        assert_eq!(Dimensions(1, 4), rotated.get_dimensions());
+    }
+
+    #[test]
+    fn test_rotate_line_by_arbitrary_angle_stays_connected() {
+        let line = Line::new(Coords(0, 0), Coords(10, 0)).unwrap();
+        let rotated = rotate(line, 30.0, Coords(0, 0)).unwrap();
+        let coords = rotated.get_coords();
+
+        assert_eq!(Coords(0, 0), coords[0]);
+        assert_eq!(Coords(9, 5), coords[coords.len() - 1]);
+
+        for pair in coords.windows(2) {
+            assert!((pair[0].0 - pair[1].0).abs() <= 1 && (pair[0].1 - pair[1].1).abs() <= 1);
+        }
+
+        assert_eq!(Some(Representation::DiagonalLineLeftToRight), rotated.get_representation_at(Coords(9, 5)));
+    }
+
+    #[test]
+    fn test_rotate_line_by_small_angle_steps_diagonally() {
+        let line = Line::new(Coords(0, 0), Coords(10, 0)).unwrap();
+        let rotated = rotate(line, 10.0, Coords(0, 0)).unwrap();
+        let coords = rotated.get_coords();
+
+        assert_eq!(Some(Representation::HorizontalLine), rotated.get_representation_at(coords[0]));
+        assert!(coords.windows(2).all(|pair| {
+            let diagonal = rotated.get_representation_at(pair[1]) == Some(Representation::DiagonalLineLeftToRight);
+            diagonal == (pair[0].1 != pair[1].1)
+        }));
+    }
+
+    #[test]
+    fn test_rotate_combined_object_around_pivot() {
+        let lines = combine(Box::new(fixtures_horizontal_line()), Box::new(fixtures_vertical_line())).unwrap();
+        let rotated = rotate(lines, -90.0, Coords(1, 1)).unwrap();
+
+        let canvas = plot(Box::new(rotated)).unwrap();
+
+        assert_eq!("| - - -\n|      \n|      \n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_rotate_circle_keeps_outline_connected() {
+        let circle = Circle::new(Coords(0, 0), 5).unwrap();
+        let coords = circle.get_coords().len();
+        let rotated = rotate(circle, 45.0, Coords(0, 0)).unwrap();
+
+        assert!(rotated.get_coords().len() >= coords - 4);
+        assert!(rotated.get_coords().iter().all(|&coords| rotated.get_representation_at(coords) == Some(Representation::Circle)));
+    }
+
+    #[test]
+    fn test_rotate_twice_uses_rotated_paths() {
+        let line = Line::new(Coords(0, 0), Coords(4, 0)).unwrap();
+        let rotated = rotate(rotate(line, 45.0, Coords(0, 0)).unwrap(), 45.0, Coords(0, 0)).unwrap();
+
+        assert_eq!(vec![Coords(0, 0), Coords(0, 1), Coords(0, 2), Coords(0, 3), Coords(0, 4)], rotated.get_coords());
+    }
+
+    #[test]
+    fn test_rotate_rectangle_maps_corners() {
+        let rectangle = Rectangle::new(Coords(0, 0), Coords(3, 2)).unwrap();
+        let canvas = plot(Box::new(rotate(rectangle, 90.0, Coords(0, 0)).unwrap())).unwrap();
+
+        assert_eq!("+ - +\n|   |\n|   |\n+ - +\n", render(&canvas, &Charset::ascii()));
+        assert_eq!(Representation::TopLeftCorner, canvas.get_representation_at(Coords(0, 3)));
+        assert_eq!(Representation::BottomRightCorner, canvas.get_representation_at(Coords(2, 0)));
+    }
+
+    #[test]
+    fn test_translate_line() {
+        let line = Line::new(Coords(0, 0), Coords(2, 0)).unwrap();
+        let translated = transform(line, Transform::translate(-2.0, 3.0)).unwrap();
+
+        assert_eq!(vec![Coords(-2, 3), Coords(-1, 3), Coords(0, 3)], translated.get_coords());
+        assert_eq!(Coords(-2, 0), translated.get_origin());
+        assert_eq!(Dimensions(3, 4), translated.get_dimensions());
+    }
+
+    #[test]
+    fn test_scale_line() {
+        let line = Line::new(Coords(1, 1), Coords(3, 1)).unwrap();
+        let scaled = transform(line, Transform::scale(2.0, 3.0)).unwrap();
+
+        assert_eq!(vec![Coords(2, 3), Coords(3, 3), Coords(4, 3), Coords(5, 3), Coords(6, 3)], scaled.get_coords());
+        assert_eq!(Dimensions(7, 4), scaled.get_dimensions());
+        assert_eq!(Some(Representation::HorizontalLine), scaled.get_representation_at(Coords(4, 3)));
+    }
+
+    #[test]
+    fn test_scale_filled_rectangle_fills_every_row() {
+        let rectangle = Rectangle::new(Coords(0, 0), Coords(4, 2)).unwrap().filled();
+        let canvas = plot(Box::new(transform(rectangle, Transform::scale(1.0, 3.0)).unwrap())).unwrap();
+
+        assert_eq!("+ - - - +\n| # # # |\n| # # # |\n| # # # |\n| # # # |\n| # # # |\n+ - - - +\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_rotate_filled_circle_has_no_holes() {
+        let circle = Circle::new(Coords(0, 0), 3).unwrap().filled();
+        let rotated = rotate(circle, 30.0, Coords(0, 0)).unwrap();
+        let coords = rotated.get_coords();
+
+        // every row is drawn from its leftmost to its rightmost cell
+        for y in -3..=3 {
+            let row = coords.iter().filter(|a| a.1 == y).map(|a| a.0).collect::<Vec<_>>();
+            let (left, right) = (*row.iter().min().unwrap(), *row.iter().max().unwrap());
+
+            assert!((left..=right).all(|x| rotated.get_representation_at(Coords(x, y)).is_some()), "hole in row {}", y);
+        }
+
+        assert_eq!(Some(Representation::Fill), rotated.get_representation_at(Coords(0, 0)));
+    }
+
+    #[test]
+    fn test_mirror_swaps_diagonals() {
+        let mirrored = transform(fixtures_diagonal_line_ltr(), Transform::mirror_horizontally().about(Coords(1, 0))).unwrap();
+
+        assert_eq!(vec![Coords(2, 0), Coords(1, 1), Coords(0, 2)], mirrored.get_coords());
+        assert_eq!(Some(Representation::DiagonalLineRightToLeft), mirrored.get_representation_at(Coords(1, 1)));
+
+        let canvas = plot(Box::new(mirrored)).unwrap();
+        assert_eq!("\\    \n  \\  \n    \\\n", render(&canvas, &Charset::ascii()));
+
+        let mirrored = transform(fixtures_diagonal_line_ltr(), Transform::mirror_vertically()).unwrap();
+        assert_eq!(Some(Representation::DiagonalLineRightToLeft), mirrored.get_representation_at(Coords(1, -1)));
+    }
+
+    #[test]
+    fn test_shear_vertical_line() {
+        let sheared = transform(fixtures_vertical_line(), Transform::shear(1.0, 0.0)).unwrap();
+
+        assert_eq!(vec![Coords(0, 0), Coords(1, 1), Coords(2, 2), Coords(3, 3)], sheared.get_coords());
+        assert_eq!(Some(Representation::DiagonalLineLeftToRight), sheared.get_representation_at(Coords(3, 3)));
+    }
+
+    #[test]
+    fn test_compose_transforms() {
+        let translate_then_scale = Transform::translate(1.0, 0.0).then(Transform::scale(2.0, 2.0));
+        let scale_then_translate = Transform::scale(2.0, 2.0).then(Transform::translate(1.0, 0.0));
+
+        assert_eq!((4.0, 2.0), translate_then_scale.apply(1.0, 1.0));
+        assert_eq!((3.0, 2.0), scale_then_translate.apply(1.0, 1.0));
+        assert_eq!((3.0, 1.0), Transform::scale(2.0, 2.0).about(Coords(1, 1)).apply(2.0, 1.0));
+        assert_eq!(Transform::identity().apply(5.0, -3.0), Transform::rotation(90.0).then(Transform::rotation(-90.0)).apply(5.0, -3.0));
+    }
+
+    #[test]
+    fn test_translate_keeps_styles_of_contained_objects() {
+        let red = Style::new().foreground(Color::Red);
+        let equals = Style::new().glyph('=');
+
+        let styled = Styled::new(fixtures_horizontal_line(), equals.clone());
+        let lines = combine(Box::new(styled), Box::new(Line::new(Coords(0, 1), Coords(2, 1)).unwrap())).unwrap();
+        let translated = transform(Styled::new(lines, red.clone()), Transform::translate(1.0, 0.0)).unwrap();
+
+        let canvas = plot(Box::new(translated)).unwrap();
+
+        assert!(canvas.1.contains(&PlottedCoords(2, 0, Representation::HorizontalLine, Some(equals))));
+        assert!(canvas.1.contains(&PlottedCoords(2, 1, Representation::HorizontalLine, Some(red))));
+        assert_eq!("  - - -\n  = = =\n", render(&canvas, &Charset::ascii()));
+    }
+
+    #[test]
+    fn test_transform_invalid_factors() {
+        assert_eq!(Err(Error::InvalidTransform("all factors have to be finite numbers")), transform(fixtures_circle(), Transform::scale(f64::INFINITY, 1.0)).map(|_| ()));
+    }
+
+    #[test]
+    fn test_rotate_invalid_angle() {
+        assert_eq!(Err(Error::UnsupportedRotation("the angle has to be a finite number")), rotate(fixtures_circle(), f64::NAN, Coords(0, 0)).map(|_| ()));
+    }
+}